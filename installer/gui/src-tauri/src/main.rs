@@ -2,8 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use tauri::{command, WebviewWindowBuilder, Window};
+use std::process::{Command, Stdio};
+use tauri::{command, AppHandle, Emitter, State, WebviewWindowBuilder};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RequirementStatus {
@@ -12,8 +15,207 @@ pub struct RequirementStatus {
     message: String,
 }
 
+/// One line of live output from a streamed setup command, emitted as a
+/// `setup-log` event so the webview can render a console instead of waiting
+/// for the process to finish.
+#[derive(Debug, Clone, Serialize)]
+struct LogLine {
+    stream: &'static str,
+    line: String,
+    step: String,
+}
+
+/// Terminal event emitted once a streamed setup command exits.
+#[derive(Debug, Clone, Serialize)]
+struct SetupFinished {
+    exit_code: Option<i32>,
+}
+
+/// Tracks the PID of the currently running streamed child process so
+/// [`cancel_setup`] can kill it from another invoke call.
+#[derive(Default)]
+pub struct RunningProcess(std::sync::Mutex<Option<u32>>);
+
+/// How to remediate a missing or outdated [`ComponentStatus`]. Carries real
+/// data (the actual command/URL) so the frontend can offer a one-click fix
+/// instead of a generic "install it yourself" message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum RepairAction {
+    PackageManager { command: String },
+    Download { url: String },
+    StartService { command: String },
+    None,
+}
+
+/// Result of probing a single tool/service: whether it's present, what
+/// version was detected, the minimum version this app needs, and how to fix
+/// it if it's missing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    installed: bool,
+    version: Option<String>,
+    min_required: Option<String>,
+    fix: RepairAction,
+}
+
+/// Machine-readable snapshot of the whole environment, returned by
+/// [`probe_environment`] so the frontend can render a checklist with
+/// per-component "Fix" buttons instead of an all-or-nothing install.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReport {
+    docker: ComponentStatus,
+    docker_service: ComponentStatus,
+    git: ComponentStatus,
+    node: ComponentStatus,
+    npm: ComponentStatus,
+    rust: ComponentStatus,
+    python3: ComponentStatus,
+    ffmpeg: ComponentStatus,
+}
+
+/// Progress update for a single component, emitted during [`run_full_setup`]
+/// so the UI can tick off or highlight individual checklist rows live.
+#[derive(Debug, Clone, Serialize)]
+struct ComponentProgress {
+    name: String,
+    status: ComponentStatus,
+}
+
+/// Structured, serializable error surfaced to the webview so the UI can branch
+/// on *what* failed and offer tailored recovery actions.
+#[derive(Debug, thiserror::Error, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum InstallerError {
+    #[error("required tool not found: {name}")]
+    RequirementMissing { name: String },
+
+    #[error("Docker is not available")]
+    DockerUnavailable,
+
+    #[error("failed to spawn {program}: {source}")]
+    CommandSpawn {
+        program: String,
+        #[serde(serialize_with = "serialize_display")]
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("build failed (exit code: {exit_code:?})")]
+    BuildFailed {
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+
+    #[error("failed to display dialog")]
+    DialogFailed,
+}
+
+/// Serializes any `Display` value (e.g. `std::io::Error`) as its string form.
+fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: std::fmt::Display,
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Runs a command to completion, mapping a spawn/IO failure into
+/// [`InstallerError::CommandSpawn`].
+fn run_command(program: &str, cmd: &mut Command) -> Result<std::process::Output, InstallerError> {
+    cmd.output().map_err(|source| InstallerError::CommandSpawn {
+        program: program.to_string(),
+        source,
+    })
+}
+
+/// Outcome of a [`run_streamed`] call. `stderr` is the captured text (useful
+/// for [`InstallerError::BuildFailed`]); stdout is only ever forwarded live
+/// as `setup-log` events, never buffered.
+struct RunOutcome {
+    status: std::process::ExitStatus,
+    stderr: String,
+}
+
+/// Spawns `cmd` asynchronously, forwards each line of its stdout/stderr to
+/// the webview as a `setup-log` event tagged with `step`, and registers its
+/// PID in `process` so [`cancel_setup`] can kill it while it runs. Unlike
+/// [`run_command`], this never blocks the invoke thread on the whole process
+/// — callers get live output instead of a single dump at the end.
+async fn run_streamed(
+    app: &AppHandle,
+    process: &RunningProcess,
+    step: &str,
+    program: &str,
+    cmd: &mut TokioCommand,
+) -> Result<RunOutcome, InstallerError> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| InstallerError::CommandSpawn {
+            program: program.to_string(),
+            source,
+        })?;
+
+    *process.0.lock().unwrap() = child.id();
+
+    let stdout = child.stdout.take().expect("child spawned with piped stdout");
+    let stderr = child.stderr.take().expect("child spawned with piped stderr");
+
+    let stdout_app = app.clone();
+    let stdout_step = step.to_string();
+    let stdout_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stdout_app.emit(
+                "setup-log",
+                LogLine {
+                    stream: "stdout",
+                    line,
+                    step: stdout_step.clone(),
+                },
+            );
+        }
+    });
+
+    let stderr_app = app.clone();
+    let stderr_step = step.to_string();
+    let stderr_task = tokio::spawn(async move {
+        let mut captured = String::new();
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stderr_app.emit(
+                "setup-log",
+                LogLine {
+                    stream: "stderr",
+                    line: line.clone(),
+                    step: stderr_step.clone(),
+                },
+            );
+            captured.push_str(&line);
+            captured.push('\n');
+        }
+        captured
+    });
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|source| InstallerError::CommandSpawn {
+            program: program.to_string(),
+            source,
+        })?;
+
+    *process.0.lock().unwrap() = None;
+    let _ = stdout_task.await;
+    let stderr = stderr_task.await.unwrap_or_default();
+
+    Ok(RunOutcome { status, stderr })
+}
+
 #[command]
-async fn check_requirements() -> Result<Vec<RequirementStatus>, String> {
+async fn check_requirements() -> Result<Vec<RequirementStatus>, InstallerError> {
     let mut requirements = Vec::new();
 
     // Check Docker
@@ -147,55 +349,206 @@ async fn check_requirements() -> Result<Vec<RequirementStatus>, String> {
     Ok(requirements)
 }
 
+/// Probes `program --version`-style output, treating a successful exit as
+/// "installed" and the first line of stdout as the detected version.
+fn probe_component(
+    program: &str,
+    version_args: &[&str],
+    min_required: Option<&str>,
+    fix: RepairAction,
+) -> ComponentStatus {
+    match Command::new(program).args(version_args).output() {
+        Ok(output) if output.status.success() => ComponentStatus {
+            installed: true,
+            version: String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .map(|line| line.trim().to_string()),
+            min_required: min_required.map(str::to_string),
+            fix: RepairAction::None,
+        },
+        _ => ComponentStatus {
+            installed: false,
+            version: None,
+            min_required: min_required.map(str::to_string),
+            fix,
+        },
+    }
+}
+
+/// Probes a background service (no meaningful version) by running a command
+/// that only succeeds while the service is reachable, e.g. `docker info`.
+fn probe_service(program: &str, args: &[&str], fix: RepairAction) -> ComponentStatus {
+    let running = Command::new(program)
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    ComponentStatus {
+        installed: running,
+        version: None,
+        min_required: None,
+        fix: if running { RepairAction::None } else { fix },
+    }
+}
+
+#[command]
+async fn probe_environment() -> Result<EnvironmentReport, InstallerError> {
+    #[cfg(target_os = "linux")]
+    let docker_fix = RepairAction::PackageManager {
+        command: "bash installer/scripts/install_docker.sh".to_string(),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let docker_fix = RepairAction::Download {
+        url: "https://www.docker.com/products/docker-desktop/".to_string(),
+    };
+
+    #[cfg(target_os = "linux")]
+    let git_fix = RepairAction::PackageManager {
+        command: "sudo apt-get install -y git".to_string(),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let git_fix = RepairAction::Download {
+        url: "https://git-scm.com/downloads".to_string(),
+    };
+
+    #[cfg(target_os = "linux")]
+    let node_fix = RepairAction::PackageManager {
+        command: "curl -fsSL https://deb.nodesource.com/setup_lts.x | sudo -E bash - && sudo apt-get install -y nodejs".to_string(),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let node_fix = RepairAction::Download {
+        url: "https://nodejs.org/en/download/".to_string(),
+    };
+
+    let rust_fix = RepairAction::PackageManager {
+        command: "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y"
+            .to_string(),
+    };
+
+    #[cfg(target_os = "linux")]
+    let python_fix = RepairAction::PackageManager {
+        command: "sudo apt-get install -y python3 python3-pip".to_string(),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let python_fix = RepairAction::Download {
+        url: "https://www.python.org/downloads/".to_string(),
+    };
+
+    #[cfg(target_os = "linux")]
+    let ffmpeg_fix = RepairAction::PackageManager {
+        command: "sudo apt-get install -y ffmpeg".to_string(),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let ffmpeg_fix = RepairAction::Download {
+        url: "https://ffmpeg.org/download.html".to_string(),
+    };
+
+    Ok(EnvironmentReport {
+        docker: probe_component("docker", &["--version"], None, docker_fix),
+        docker_service: probe_service(
+            "docker",
+            &["info"],
+            RepairAction::StartService {
+                command: "sudo systemctl start docker".to_string(),
+            },
+        ),
+        git: probe_component("git", &["--version"], None, git_fix),
+        node: probe_component("node", &["--version"], Some("18.0.0"), node_fix.clone()),
+        npm: probe_component("npm", &["--version"], None, node_fix),
+        rust: probe_component("cargo", &["--version"], Some("1.70.0"), rust_fix),
+        python3: probe_component("python3", &["--version"], Some("3.8"), python_fix),
+        ffmpeg: probe_component("ffmpeg", &["-version"], None, ffmpeg_fix),
+    })
+}
+
 #[command]
-async fn install_requirement(requirement: String) -> Result<bool, String> {
-    match requirement.as_str() {
+async fn install_requirement(
+    app: AppHandle,
+    process: State<'_, RunningProcess>,
+    requirement: String,
+) -> Result<bool, InstallerError> {
+    let step = requirement.as_str();
+    let result: Result<bool, InstallerError> = match requirement.as_str() {
         "Docker" => {
             #[cfg(target_os = "linux")]
             {
                 let install_script = include_str!("../scripts/install_docker.sh");
-                std::fs::write("/tmp/install_docker.sh", install_script)
-                    .map_err(|e| e.to_string())?;
-
-                Command::new("chmod")
-                    .args(["+x", "/tmp/install_docker.sh"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
-
-                Command::new("bash")
-                    .arg("/tmp/install_docker.sh")
-                    .output()
-                    .map_err(|e| e.to_string())?;
+                std::fs::write("/tmp/install_docker.sh", install_script).map_err(|source| {
+                    InstallerError::CommandSpawn {
+                        program: "write install_docker.sh".to_string(),
+                        source,
+                    }
+                })?;
+
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "chmod",
+                    TokioCommand::new("chmod").args(["+x", "/tmp/install_docker.sh"]),
+                )
+                .await?;
+
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "bash",
+                    TokioCommand::new("bash").arg("/tmp/install_docker.sh"),
+                )
+                .await?;
             }
             Ok(true)
         }
         "Git" => {
             #[cfg(target_os = "linux")]
             {
-                Command::new("sudo")
-                    .args(["apt-get", "update"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
-
-                Command::new("sudo")
-                    .args(["apt-get", "install", "-y", "git"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "sudo",
+                    TokioCommand::new("sudo").args(["apt-get", "update"]),
+                )
+                .await?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "sudo",
+                    TokioCommand::new("sudo").args(["apt-get", "install", "-y", "git"]),
+                )
+                .await?;
             }
             Ok(true)
         }
         "Node.js" => {
             #[cfg(target_os = "linux")]
             {
-                Command::new("curl")
-                    .args(["-fsSL", "https://deb.nodesource.com/setup_lts.x", "|", "sudo", "-E", "bash", "-"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
-
-                Command::new("sudo")
-                    .args(["apt-get", "install", "-y", "nodejs"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "curl",
+                    TokioCommand::new("curl").args([
+                        "-fsSL",
+                        "https://deb.nodesource.com/setup_lts.x",
+                        "|",
+                        "sudo",
+                        "-E",
+                        "bash",
+                        "-",
+                    ]),
+                )
+                .await?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "sudo",
+                    TokioCommand::new("sudo").args(["apt-get", "install", "-y", "nodejs"]),
+                )
+                .await?;
             }
             Ok(true)
         }
@@ -204,66 +557,109 @@ async fn install_requirement(requirement: String) -> Result<bool, String> {
             Ok(true)
         }
         "Rust" => {
-            Command::new("curl")
-                .args(["--proto", "=https", "--tlsv1.2", "-sSf", "https://sh.rustup.rs", "|", "sh", "-s", "--", "-y"])
-                .output()
-                .map_err(|e| e.to_string())?;
+            run_streamed(
+                &app,
+                &process,
+                step,
+                "curl",
+                TokioCommand::new("curl").args([
+                    "--proto", "=https", "--tlsv1.2", "-sSf", "https://sh.rustup.rs", "|", "sh",
+                    "-s", "--", "-y",
+                ]),
+            )
+            .await?;
             Ok(true)
         }
         "Python3" => {
             #[cfg(target_os = "linux")]
             {
-                Command::new("sudo")
-                    .args(["apt-get", "install", "-y", "python3", "python3-pip"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "sudo",
+                    TokioCommand::new("sudo")
+                        .args(["apt-get", "install", "-y", "python3", "python3-pip"]),
+                )
+                .await?;
             }
             Ok(true)
         }
         "FFmpeg" => {
             #[cfg(target_os = "linux")]
             {
-                Command::new("sudo")
-                    .args(["apt-get", "install", "-y", "ffmpeg"])
-                    .output()
-                    .map_err(|e| e.to_string())?;
+                run_streamed(
+                    &app,
+                    &process,
+                    step,
+                    "sudo",
+                    TokioCommand::new("sudo").args(["apt-get", "install", "-y", "ffmpeg"]),
+                )
+                .await?;
             }
             Ok(true)
         }
-        _ => Err("Unsupported requirement".to_string()),
-    }
+        other => Err(InstallerError::RequirementMissing {
+            name: other.to_string(),
+        }),
+    };
+
+    let exit_code = if result.is_ok() { Some(0) } else { None };
+    let _ = app.emit("setup-finished", SetupFinished { exit_code });
+    result
+}
+
+#[command]
+async fn cancel_setup(process: State<'_, RunningProcess>) -> Result<(), InstallerError> {
+    let Some(pid) = process.0.lock().unwrap().take() else {
+        return Ok(());
+    };
+
+    #[cfg(unix)]
+    run_command("kill", Command::new("kill").args(["-9", &pid.to_string()]))?;
+    #[cfg(windows)]
+    run_command(
+        "taskkill",
+        Command::new("taskkill").args(["/PID", &pid.to_string(), "/F"]),
+    )?;
+
+    Ok(())
 }
 
 #[command]
-async fn start_docker_service() -> Result<bool, String> {
+async fn start_docker_service() -> Result<bool, InstallerError> {
     #[cfg(target_os = "linux")]
     {
-        Command::new("sudo")
-            .args(["systemctl", "start", "docker"])
-            .output()
-            .map_err(|e| e.to_string())?;
+        run_command(
+            "sudo",
+            Command::new("sudo").args(["systemctl", "start", "docker"]),
+        )?;
     }
     Ok(true)
 }
 
 #[command]
-async fn create_project_files(project_path: String, install_path: String) -> Result<(), String> {
+async fn create_project_files(project_path: String, install_path: String) -> Result<(), InstallerError> {
     println!("Creating project files in: {}", project_path);
     println!("Installation path: {}", install_path);
 
     // Create project directory if it doesn't exist
-    std::fs::create_dir_all(&project_path)
-        .map_err(|e| {
-            println!("Failed to create project directory: {}", e);
-            format!("Failed to create project directory '{}': {}", project_path, e)
-        })?;
+    std::fs::create_dir_all(&project_path).map_err(|source| {
+        println!("Failed to create project directory: {}", source);
+        InstallerError::CommandSpawn {
+            program: format!("create_dir_all {project_path}"),
+            source,
+        }
+    })?;
 
     // Create installation directory if it doesn't exist
-    std::fs::create_dir_all(&install_path)
-        .map_err(|e| {
-            println!("Failed to create installation directory: {}", e);
-            format!("Failed to create installation directory '{}': {}", install_path, e)
-        })?;
+    std::fs::create_dir_all(&install_path).map_err(|source| {
+        println!("Failed to create installation directory: {}", source);
+        InstallerError::CommandSpawn {
+            program: format!("create_dir_all {install_path}"),
+            source,
+        }
+    })?;
 
     // Copy project files to the project directory
     let source_files = [
@@ -280,9 +676,14 @@ async fn create_project_files(project_path: String, install_path: String) -> Res
     for file in source_files.iter() {
         let source = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
             .parent()
-            .ok_or("Cannot find parent directory")?
-            .parent()
-            .ok_or("Cannot find project root")?
+            .and_then(std::path::Path::parent)
+            .ok_or_else(|| InstallerError::CommandSpawn {
+                program: "resolve project root".to_string(),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "cannot find project root",
+                ),
+            })?
             .join(file);
 
         let destination = std::path::Path::new(&project_path).join(file);
@@ -315,7 +716,10 @@ async fn create_project_files(project_path: String, install_path: String) -> Res
             copied_files.join(", ")
         );
         println!("{}", error_summary);
-        return Err(error_summary);
+        return Err(InstallerError::CommandSpawn {
+            program: "copy project files".to_string(),
+            source: std::io::Error::new(std::io::ErrorKind::Other, error_summary),
+        });
     }
 
     println!("Project files created successfully. Copied files: {}", copied_files.join(", "));
@@ -323,50 +727,57 @@ async fn create_project_files(project_path: String, install_path: String) -> Res
 }
 
 #[command]
-async fn run_build_script(path: String) -> Result<String, String> {
+async fn run_build_script(
+    app: AppHandle,
+    process: State<'_, RunningProcess>,
+    path: String,
+) -> Result<(), InstallerError> {
     // First ensure we're in the project directory
-    std::env::set_current_dir(&path)
-        .map_err(|e| format!("Failed to change to project directory: {}", e))?;
+    std::env::set_current_dir(&path).map_err(|source| InstallerError::CommandSpawn {
+        program: format!("chdir {path}"),
+        source,
+    })?;
 
     println!("Running build script in directory: {:?}", std::env::current_dir());
 
-    let output = Command::new("bash")
-        .arg("-c")
-        .arg("./build.sh")
-        .output()
-        .map_err(|e| format!("Failed to run build script: {}", e))?;
-
-    // Combine stdout and stderr for complete output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-
-    let full_output = format!("{}\n{}", stdout, stderr);
+    // Output streams live as `setup-log` events instead of being buffered
+    // until the build finishes, so a multi-minute cargo build shows progress.
+    let outcome = run_streamed(
+        &app,
+        &process,
+        "build",
+        "build.sh",
+        TokioCommand::new("bash").arg("-c").arg("./build.sh"),
+    )
+    .await?;
 
-    println!("Build script output: {}", full_output);
+    let exit_code = outcome.status.code();
+    let _ = app.emit("setup-finished", SetupFinished { exit_code });
 
-    // Check if the command was successful
-    if output.status.success() {
-        Ok(full_output)
+    if outcome.status.success() {
+        Ok(())
     } else {
-        let error_msg = format!("Build script failed with exit code: {:?}\nOutput: {}",
-                               output.status.code(), full_output);
-        println!("Build error: {}", error_msg);
-        Err(error_msg)
+        let error = InstallerError::BuildFailed {
+            exit_code,
+            stderr: outcome.stderr,
+        };
+        println!("Build error: {}", error);
+        Err(error)
     }
 }
 
 #[command]
-async fn launch_docker_app(project_path: String) -> Result<(), String> {
+async fn launch_docker_app(project_path: String) -> Result<(), InstallerError> {
     println!("Attempting to launch Docker app from project path: {}", project_path);
 
     // Check if Docker image exists
-    let check_image = Command::new("docker")
-        .args(["images", "-q", "tiktok-clip-studio"])
-        .output()
-        .map_err(|e| format!("Failed to check Docker images: {}", e))?;
+    let check_image = run_command(
+        "docker",
+        Command::new("docker").args(["images", "-q", "tiktok-clip-studio"]),
+    )?;
 
     if check_image.stdout.is_empty() {
-        return Err("Docker image 'tiktok-clip-studio' not found. Please ensure the Docker build was successful.".to_string());
+        return Err(InstallerError::DockerUnavailable);
     }
 
     println!("Found Docker image, launching container");
@@ -392,7 +803,10 @@ async fn launch_docker_app(project_path: String) -> Result<(), String> {
             "tiktok-clip-studio"
         ])
         .spawn()
-        .map_err(|e| format!("Failed to launch Docker container: {}", e))?;
+        .map_err(|source| InstallerError::CommandSpawn {
+            program: "docker run".to_string(),
+            source,
+        })?;
 
     println!("Docker container launched successfully with PID: {:?}", output.id());
 
@@ -400,7 +814,7 @@ async fn launch_docker_app(project_path: String) -> Result<(), String> {
 }
 
 #[command]
-async fn launch_built_app(project_path: String) -> Result<(), String> {
+async fn launch_built_app(project_path: String) -> Result<(), InstallerError> {
     use std::path::Path;
 
     println!("Attempting to launch app from project path: {}", project_path);
@@ -413,16 +827,19 @@ async fn launch_built_app(project_path: String) -> Result<(), String> {
         println!("Found launcher script, using it to launch app");
         #[cfg(unix)]
         {
-            Command::new("chmod")
-                .args(["+x", launcher_path.to_str().unwrap()])
-                .output()
-                .map_err(|e| format!("Failed to make launcher executable: {}", e))?;
+            run_command(
+                "chmod",
+                Command::new("chmod").args(["+x", launcher_path.to_str().unwrap()]),
+            )?;
         }
 
         Command::new("bash")
             .arg(launcher_path.to_str().unwrap())
             .spawn()
-            .map_err(|e| format!("Failed to launch application with launcher: {}", e))?;
+            .map_err(|source| InstallerError::CommandSpawn {
+                program: "bash launch-tiktok-app.sh".to_string(),
+                source,
+            })?;
 
         return Ok(());
     }
@@ -435,15 +852,18 @@ async fn launch_built_app(project_path: String) -> Result<(), String> {
         println!("Found Tauri executable, launching directly");
         #[cfg(unix)]
         {
-            Command::new("chmod")
-                .args(["+x", tauri_app_path.to_str().unwrap()])
-                .output()
-                .map_err(|e| format!("Failed to make app executable: {}", e))?;
+            run_command(
+                "chmod",
+                Command::new("chmod").args(["+x", tauri_app_path.to_str().unwrap()]),
+            )?;
         }
 
         Command::new(tauri_app_path)
             .spawn()
-            .map_err(|e| format!("Failed to launch Tauri application: {}", e))?;
+            .map_err(|source| InstallerError::CommandSpawn {
+                program: "tiktok-clip-studio".to_string(),
+                source,
+            })?;
 
         return Ok(());
     }
@@ -456,15 +876,18 @@ async fn launch_built_app(project_path: String) -> Result<(), String> {
         println!("Found app in dist directory");
         #[cfg(unix)]
         {
-            Command::new("chmod")
-                .args(["+x", dist_app_path.to_str().unwrap()])
-                .output()
-                .map_err(|e| format!("Failed to make app executable: {}", e))?;
+            run_command(
+                "chmod",
+                Command::new("chmod").args(["+x", dist_app_path.to_str().unwrap()]),
+            )?;
         }
 
         Command::new(dist_app_path)
             .spawn()
-            .map_err(|e| format!("Failed to launch application from dist: {}", e))?;
+            .map_err(|source| InstallerError::CommandSpawn {
+                program: "dist/tiktok-clip-studio".to_string(),
+                source,
+            })?;
 
         return Ok(());
     }
@@ -480,11 +903,18 @@ async fn launch_built_app(project_path: String) -> Result<(), String> {
     );
 
     println!("{}", error_msg);
-    Err(error_msg)
+    Err(InstallerError::CommandSpawn {
+        program: "locate built application".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, error_msg),
+    })
 }
 
 #[command]
-async fn run_full_setup(sudoPassword: String) -> Result<String, String> {
+async fn run_full_setup(
+    app: AppHandle,
+    process: State<'_, RunningProcess>,
+    sudoPassword: String,
+) -> Result<String, InstallerError> {
     println!("Tauri command run_full_setup called with password length: {}", sudoPassword.len());
 
     // Write progress updates to file
@@ -493,9 +923,13 @@ async fn run_full_setup(sudoPassword: String) -> Result<String, String> {
     let log_file = "/tmp/installer.log";
 
     // Clear previous files
-    std::fs::write(progress_file, "").map_err(|e| format!("Failed to clear progress file: {}", e))?;
-    std::fs::write(error_file, "").map_err(|e| format!("Failed to clear error file: {}", e))?;
-    std::fs::write(log_file, "").map_err(|e| format!("Failed to clear log file: {}", e))?;
+    let clear = |path: &str, source: std::io::Error| InstallerError::CommandSpawn {
+        program: format!("clear {path}"),
+        source,
+    };
+    std::fs::write(progress_file, "").map_err(|e| clear(progress_file, e))?;
+    std::fs::write(error_file, "").map_err(|e| clear(error_file, e))?;
+    std::fs::write(log_file, "").map_err(|e| clear(log_file, e))?;
 
     // Helper function to log messages
     let log = |level: &str, message: &str| {
@@ -526,82 +960,151 @@ async fn run_full_setup(sudoPassword: String) -> Result<String, String> {
         std::fs::write(progress_file, progress_entry).ok();
     };
 
+    // Each step below streams its output live as `setup-log` events (instead
+    // of returning one blocking dump at the end) and fails fast, recording
+    // the offending step's stderr to `error_file` as before.
+    macro_rules! fail_step {
+        ($outcome:expr, $step:expr, $message:expr) => {
+            if !$outcome.status.success() {
+                let stderr = $outcome.stderr;
+                std::fs::write(error_file, format!("{{\"error\": \"{}: {}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", $message, stderr)).ok();
+                let _ = app.emit("setup-finished", SetupFinished { exit_code: $outcome.status.code() });
+                return Err(InstallerError::BuildFailed {
+                    exit_code: $outcome.status.code(),
+                    stderr,
+                });
+            }
+        };
+    }
+
+    // Probe what's already present so a re-run after a partial failure only
+    // redoes the steps that are actually missing, and emit a per-component
+    // status so the frontend checklist can tick off what's already satisfied.
+    let report = probe_environment().await?;
+    let emit_component = |name: &str, status: &ComponentStatus| {
+        let _ = app.emit(
+            "component-status",
+            ComponentProgress {
+                name: name.to_string(),
+                status: status.clone(),
+            },
+        );
+    };
+    emit_component("docker", &report.docker);
+    emit_component("docker_service", &report.docker_service);
+    emit_component("ffmpeg", &report.ffmpeg);
+
     // Install system dependencies
     #[cfg(target_os = "linux")]
     {
-        log("INFO", "Starting system dependencies installation");
-        update_progress("Installing system dependencies...");
-
-        log("INFO", "Updating package list");
-        let output = Command::new("echo")
-            .args([sudoPassword.as_str(), "|", "sudo", "-S", "apt-get", "update"])
-            .output()
-            .map_err(|e| format!("Failed to update package list: {}", e))?;
-
-        if !output.status.success() {
-            let error_msg = format!("Package update failed: {}", String::from_utf8_lossy(&output.stderr));
-            std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-            return Err(error_msg);
-        }
-
-        log("SUCCESS", "Package list updated successfully");
-        log("INFO", "Installing system packages");
-        let output = Command::new("echo")
-            .args([sudoPassword.as_str(), "|", "sudo", "-S", "apt-get", "install", "-y", "build-essential", "curl", "wget", "git", "python3", "python3-pip", "nodejs", "npm"])
-            .output()
-            .map_err(|e| format!("Failed to install system dependencies: {}", e))?;
-
-        if !output.status.success() {
-            let error_msg = format!("System dependencies installation failed: {}", String::from_utf8_lossy(&output.stderr));
-            std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-            return Err(error_msg);
+        let missing: Vec<&str> = [
+            (!report.git.installed).then_some("git"),
+            (!report.python3.installed).then_some("python3"),
+            (!report.python3.installed).then_some("python3-pip"),
+            (!report.node.installed).then_some("nodejs"),
+            (!report.node.installed).then_some("npm"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        emit_component("git", &report.git);
+        emit_component("python3", &report.python3);
+        emit_component("node", &report.node);
+        emit_component("npm", &report.npm);
+
+        if missing.is_empty() {
+            log("SUCCESS", "System dependencies already satisfied, skipping");
+        } else {
+            log("INFO", "Starting system dependencies installation");
+            update_progress("Installing system dependencies...");
+
+            log("INFO", "Updating package list");
+            let outcome = run_streamed(
+                &app,
+                &process,
+                "apt-get update",
+                "apt-get update",
+                TokioCommand::new("echo").args([sudoPassword.as_str(), "|", "sudo", "-S", "apt-get", "update"]),
+            )
+            .await?;
+            fail_step!(outcome, "apt-get update", "Package update failed");
+
+            log("SUCCESS", "Package list updated successfully");
+            log("INFO", &format!("Installing missing packages: {}", missing.join(", ")));
+
+            let mut install_args = vec![
+                sudoPassword.clone(),
+                "|".to_string(),
+                "sudo".to_string(),
+                "-S".to_string(),
+                "apt-get".to_string(),
+                "install".to_string(),
+                "-y".to_string(),
+                "build-essential".to_string(),
+                "curl".to_string(),
+                "wget".to_string(),
+            ];
+            install_args.extend(missing.iter().map(|s| s.to_string()));
+
+            let outcome = run_streamed(
+                &app,
+                &process,
+                "apt-get install",
+                "apt-get install",
+                TokioCommand::new("echo").args(&install_args),
+            )
+            .await?;
+            fail_step!(outcome, "apt-get install", "System dependencies installation failed");
+
+            log("SUCCESS", "System dependencies installed successfully");
         }
-
-        log("SUCCESS", "System dependencies installed successfully");
     }
 
     // Install Rust
-    log("INFO", "Installing Rust programming language");
-    update_progress("Installing Rust programming language...");
-
-    let output = Command::new("curl")
-        .args(["--proto", "=https", "--tlsv1.2", "-sSf", "https://sh.rustup.rs", "|", "sh", "-s", "--", "-y"])
-        .output()
-        .map_err(|e| format!("Failed to install Rust: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = format!("Rust installation failed: {}", String::from_utf8_lossy(&output.stderr));
-        std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-        return Err(error_msg);
+    if report.rust.installed {
+        log("SUCCESS", "Rust already installed, skipping");
+    } else {
+        log("INFO", "Installing Rust programming language");
+        update_progress("Installing Rust programming language...");
+
+        let outcome = run_streamed(
+            &app,
+            &process,
+            "rustup-init",
+            "rustup-init",
+            TokioCommand::new("curl").args(["--proto", "=https", "--tlsv1.2", "-sSf", "https://sh.rustup.rs", "|", "sh", "-s", "--", "-y"]),
+        )
+        .await?;
+        fail_step!(outcome, "rustup-init", "Rust installation failed");
+
+        log("SUCCESS", "Rust installed successfully");
     }
-
-    log("SUCCESS", "Rust installed successfully");
+    emit_component("rust", &report.rust);
 
     // Install Python dependencies
     log("INFO", "Installing Python dependencies");
     update_progress("Installing Python dependencies...");
 
-    let output = Command::new("python3")
-        .args(["-m", "pip", "install", "--upgrade", "pip"])
-        .output()
-        .map_err(|e| format!("Failed to upgrade pip: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = format!("Pip upgrade failed: {}", String::from_utf8_lossy(&output.stderr));
-        std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-        return Err(error_msg);
-    }
-
-    let output = Command::new("python3")
-        .args(["-m", "pip", "install", "-r", "requirements.txt"])
-        .output()
-        .map_err(|e| format!("Failed to install Python dependencies: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = format!("Python dependencies installation failed: {}", String::from_utf8_lossy(&output.stderr));
-        std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-        return Err(error_msg);
-    }
+    let outcome = run_streamed(
+        &app,
+        &process,
+        "pip install --upgrade pip",
+        "pip install --upgrade pip",
+        TokioCommand::new("python3").args(["-m", "pip", "install", "--upgrade", "pip"]),
+    )
+    .await?;
+    fail_step!(outcome, "pip install --upgrade pip", "Pip upgrade failed");
+
+    let outcome = run_streamed(
+        &app,
+        &process,
+        "pip install -r requirements.txt",
+        "pip install -r requirements.txt",
+        TokioCommand::new("python3").args(["-m", "pip", "install", "-r", "requirements.txt"]),
+    )
+    .await?;
+    fail_step!(outcome, "pip install -r requirements.txt", "Python dependencies installation failed");
 
     log("SUCCESS", "Python dependencies installed successfully");
 
@@ -609,16 +1112,15 @@ async fn run_full_setup(sudoPassword: String) -> Result<String, String> {
     log("INFO", "Installing Node.js dependencies");
     update_progress("Installing Node.js dependencies...");
 
-    let output = Command::new("npm")
-        .arg("install")
-        .output()
-        .map_err(|e| format!("Failed to install Node.js dependencies: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = format!("Node.js dependencies installation failed: {}", String::from_utf8_lossy(&output.stderr));
-        std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-        return Err(error_msg);
-    }
+    let outcome = run_streamed(
+        &app,
+        &process,
+        "npm install",
+        "npm install",
+        TokioCommand::new("npm").arg("install"),
+    )
+    .await?;
+    fail_step!(outcome, "npm install", "Node.js dependencies installation failed");
 
     log("SUCCESS", "Node.js dependencies installed successfully");
 
@@ -626,105 +1128,79 @@ async fn run_full_setup(sudoPassword: String) -> Result<String, String> {
     log("INFO", "Installing Tauri CLI");
     update_progress("Installing Tauri CLI...");
 
-    let output = Command::new("cargo")
-        .args(["install", "tauri-cli"])
-        .output()
-        .map_err(|e| format!("Failed to install Tauri CLI: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = format!("Tauri CLI installation failed: {}", String::from_utf8_lossy(&output.stderr));
-        std::fs::write(error_file, format!("{{\"error\": \"{}\", \"timestamp\": \"$(date '+%Y-%m-%d %H:%M:%S')\"}}", error_msg)).ok();
-        return Err(error_msg);
-    }
+    let outcome = run_streamed(
+        &app,
+        &process,
+        "cargo install tauri-cli",
+        "cargo install tauri-cli",
+        TokioCommand::new("cargo").args(["install", "tauri-cli"]),
+    )
+    .await?;
+    fail_step!(outcome, "cargo install tauri-cli", "Tauri CLI installation failed");
 
     log("SUCCESS", "Tauri CLI installed successfully");
     log("SUCCESS", "Full setup completed successfully!");
     update_progress("Setup completed successfully!");
 
+    let _ = app.emit("setup-finished", SetupFinished { exit_code: Some(0) });
     Ok("Full setup completed successfully!".to_string())
 }
 
 #[command]
-async fn show_styled_popup(window: Window, title: String, message: String, popup_type: String, icon: String) -> Result<(), String> {
-    use std::process::Command;
-
-    // Use system commands to show native dialogs
-    let result = match popup_type.as_str() {
-        "success" => {
-            // Try macOS first, then Linux GUI tools, fallback to echo
-            Command::new("osascript")
-                .args(&["-e", &format!("display dialog \"{}\" with title \"{}\" buttons {{\"OK\"}} default button \"OK\"", message, title)])
-                .output()
-                .or_else(|_| {
-                    Command::new("zenity")
-                        .args(&["--info", "--title", &title, "--text", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("kdialog")
-                        .args(&["--msgbox", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("echo")
-                        .arg(&format!("[SUCCESS] {}: {}", title, message))
-                        .output()
-                })
-        }
-        "error" => {
-            // Try macOS first, then Linux GUI tools, fallback to echo
-            Command::new("osascript")
-                .args(&["-e", &format!("display dialog \"{}\" with title \"{}\" buttons {{\"OK\"}} default button \"OK\" with icon stop", message, title)])
-                .output()
-                .or_else(|_| {
-                    Command::new("zenity")
-                        .args(&["--error", "--title", &title, "--text", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("kdialog")
-                        .args(&["--error", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("echo")
-                        .arg(&format!("[ERROR] {}: {}", title, message))
-                        .output()
-                })
-        }
-        _ => {
-            // Default to info dialog
-            Command::new("osascript")
-                .args(&["-e", &format!("display dialog \"{}\" with title \"{}\" buttons {{\"OK\"}} default button \"OK\"", message, title)])
-                .output()
-                .or_else(|_| {
-                    Command::new("zenity")
-                        .args(&["--info", "--title", &title, "--text", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("kdialog")
-                        .args(&["--msgbox", &message])
-                        .output()
-                })
-                .or_else(|_| {
-                    Command::new("echo")
-                        .arg(&format!("[INFO] {}: {}", title, message))
-                        .output()
-                })
-        }
+async fn show_styled_popup(
+    app: tauri::AppHandle,
+    title: String,
+    message: String,
+    popup_type: String,
+    icon: String,
+) -> Result<(), InstallerError> {
+    // `icon` is retained for frontend compatibility but the native dialog
+    // picks its glyph from the message kind.
+    let _ = icon;
+
+    let kind = match popup_type.as_str() {
+        "error" => MessageDialogKind::Error,
+        "warning" => MessageDialogKind::Warning,
+        _ => MessageDialogKind::Info,
     };
 
-    match result {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to show dialog: {}", e))
-    }
+    // Native modal on all three platforms; no string interpolation into a shell.
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(kind)
+        .blocking_show();
+
+    Ok(())
+}
+
+#[command]
+async fn ask_styled_popup(
+    app: tauri::AppHandle,
+    title: String,
+    message: String,
+) -> Result<bool, InstallerError> {
+    // Resolve the OK/Cancel decision asynchronously (like window.confirm).
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    app.dialog()
+        .message(message)
+        .title(title)
+        .buttons(MessageDialogButtons::OkCancel)
+        .show(move |confirmed| {
+            let _ = tx.send(confirmed);
+        });
+
+    rx.await.map_err(|_| InstallerError::DialogFailed)
 }
 
 fn main() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
+        .manage(RunningProcess::default())
         .invoke_handler(tauri::generate_handler![
             check_requirements,
+            probe_environment,
             install_requirement,
             start_docker_service,
             create_project_files,
@@ -732,7 +1208,9 @@ fn main() {
             launch_built_app,
             launch_docker_app,
             run_full_setup,
-            show_styled_popup
+            cancel_setup,
+            show_styled_popup,
+            ask_styled_popup
         ])
         .setup(|app| {
             // Create the main window
@@ -1,7 +1,11 @@
 // Clip detection and extraction commands
+use crate::settings::{ToolConfig, ToolConfigState};
 use crate::video::clipper;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use tauri::Emitter;
+use tokio::sync::Semaphore;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipInfo {
@@ -15,28 +19,48 @@ pub struct ClipInfo {
 pub async fn detect_clips(
     app: tauri::AppHandle,
     video_path: String,
-    method: Option<String>, // "scene" or "time"
+    method: Option<String>, // "scene", "silence", or "time"
+    noise_threshold_db: Option<f64>,
+    min_silence_duration: Option<f64>,
+    top_n: Option<usize>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<Vec<ClipInfo>, String> {
     let method = method.unwrap_or_else(|| "scene".to_string());
-    
+    let tools = tool_config.snapshot();
+
     let _ = app.emit("export_log", "🔍 Starting clip detection...");
-    
-    let clips = if method == "scene" {
+
+    let mut clips = if method == "scene" {
         // Scene-based detection (intelligent)
         let min_duration = 15.0;  // 15 seconds minimum
         let max_duration = 60.0;  // 60 seconds maximum
         let threshold = 0.3;      // Scene detection sensitivity
-        
-        clipper::detect_clips_by_scenes(&app, &video_path, min_duration, max_duration, threshold).await?
+
+        clipper::detect_clips_by_scenes(&app, &video_path, min_duration, max_duration, threshold, &tools).await?
+    } else if method == "silence" {
+        // Silence-based detection (auto-editor style, best for talking-head content)
+        let min_duration = 15.0; // 15 seconds minimum
+        let max_duration = 60.0; // 60 seconds maximum
+        let noise_threshold_db = noise_threshold_db.unwrap_or(-30.0);
+        let min_silence_duration = min_silence_duration.unwrap_or(0.5);
+
+        clipper::detect_clips_by_silence(&app, &video_path, min_duration, max_duration, noise_threshold_db, min_silence_duration, &tools).await?
     } else {
         // Time-based detection (fallback)
         let _ = app.emit("export_log", "📊 Getting video duration...");
-        let duration = clipper::get_video_duration(&video_path).await?;
+        let duration = clipper::get_video_duration(&video_path, &tools).await?;
         let _ = app.emit("export_log", format!("✓ Video duration: {duration:.1}s"));
-        
-        clipper::detect_clips_by_time(duration, 30.0, 5.0)
+
+        let mut clips = clipper::detect_clips_by_time(duration, 30.0, 5.0);
+        clipper::score_and_rank_clips(&app, &video_path, &mut clips, &[], &tools).await?;
+        clips
     };
-    
+
+    if let Some(top_n) = top_n {
+        clips.truncate(top_n);
+        let _ = app.emit("export_log", format!("✂️ Keeping top {} clip(s) by score", clips.len()));
+    }
+
     // Convert to ClipInfo
     let clip_infos: Vec<ClipInfo> = clips.iter().map(|c| ClipInfo {
         start_time: c.start_time,
@@ -44,7 +68,7 @@ pub async fn detect_clips(
         duration: c.duration,
         score: c.score,
     }).collect();
-    
+
     let _ = app.emit("export_log", format!("✅ Found {} clips!", clip_infos.len()));
     
     Ok(clip_infos)
@@ -58,20 +82,40 @@ pub async fn extract_and_export_clip(
     start_time: f64,
     duration: f64,
     with_subtitles: Option<bool>,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<String, String> {
+    let tools = tool_config.snapshot();
+    export_clip(&app, &input, &output, start_time, duration, with_subtitles, target_vmaf, &tools).await
+}
+
+/// Shared extraction logic behind [`extract_and_export_clip`] and
+/// [`batch_export_clips`]'s worker tasks, taking an already-resolved
+/// [`ToolConfig`] so batch export can snapshot it once up front instead of
+/// borrowing Tauri-managed state from inside spawned tasks.
+async fn export_clip(
+    app: &tauri::AppHandle,
+    input: &str,
+    output: &str,
+    start_time: f64,
+    duration: f64,
+    with_subtitles: Option<bool>,
+    target_vmaf: Option<f64>,
+    tools: &ToolConfig,
 ) -> Result<String, String> {
     let _ = app.emit("export_log", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     let _ = app.emit("export_log", format!("🎥 Processing clip: {start_time:.1}s ({duration:.1}s duration)"));
-    
+
     // Extract the clip
-    clipper::extract_clip(&app, &input, &output, start_time, duration).await?;
-    
+    clipper::extract_clip(app, input, output, start_time, duration, target_vmaf, tools).await?;
+
     // TODO: Add subtitle support if requested
     if with_subtitles.unwrap_or(false) {
         let _ = app.emit("export_log", "ℹ️ Subtitle support coming soon");
     }
-    
+
     let _ = app.emit("export_log", format!("✅ Clip saved to: {output}"));
-    Ok(output)
+    Ok(output.to_string())
 }
 
 #[tauri::command]
@@ -81,38 +125,93 @@ pub async fn batch_export_clips(
     output_dir: String,
     clips: Vec<ClipInfo>,
     with_subtitles: Option<bool>,
+    max_workers: Option<usize>,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<Vec<String>, String> {
+    let tools = Arc::new(tool_config.snapshot());
     let _ = app.emit("export_log", "═══════════════════════════════════════════");
     let _ = app.emit("export_log", format!("🚀 Starting batch export: {} clips", clips.len()));
     let _ = app.emit("export_log", format!("📁 Output directory: {output_dir}"));
     let _ = app.emit("export_log", "═══════════════════════════════════════════");
-    
+
     // Create output directory if it doesn't exist
     let _ = app.emit("export_log", "📂 Creating output directory...");
     tokio::fs::create_dir_all(&output_dir)
         .await
         .map_err(|e| format!("Failed to create output directory: {e}"))?;
     let _ = app.emit("export_log", "✓ Output directory ready");
-    
-    let mut exported_files = Vec::new();
-
-    for (index, clip) in clips.iter().enumerate() {
-        let _ = app.emit("export_log", format!("\n📦 Clip {}/{}: {:.1}s - {:.1}s", index + 1, clips.len(), clip.start_time, clip.end_time));
-        let output_path = format!("{}/clip_{:03}.mp4", output_dir, index + 1);
-        
-        extract_and_export_clip(
-            app.clone(),
-            input.clone(),
-            output_path.clone(),
-            clip.start_time,
-            clip.duration,
-            with_subtitles,
-        )
-        .await?;
-
-        exported_files.push(output_path);
+
+    // Default concurrency mirrors Av1an's worker model: one FFmpeg extraction
+    // per available core unless the caller overrides it.
+    let max_workers = max_workers.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(std::num::NonZeroUsize::get)
+            .unwrap_or(1)
+    });
+    let _ = app.emit("export_log", format!("⚙️ Using up to {max_workers} parallel workers"));
+
+    let total = clips.len();
+    let semaphore = Arc::new(Semaphore::new(max_workers));
+    let next_worker = Arc::new(AtomicUsize::new(0));
+    let input = Arc::new(input);
+    let output_dir = Arc::new(output_dir);
+
+    let tasks: Vec<_> = clips
+        .into_iter()
+        .enumerate()
+        .map(|(index, clip)| {
+            let app = app.clone();
+            let input = input.clone();
+            let output_dir = output_dir.clone();
+            let semaphore = semaphore.clone();
+            let next_worker = next_worker.clone();
+            let tools = tools.clone();
+
+            tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("export worker semaphore was closed");
+                let worker = next_worker.fetch_add(1, Ordering::SeqCst) % max_workers + 1;
+
+                let output_path = format!("{}/clip_{:03}.mp4", output_dir, index + 1);
+                let _ = app.emit(
+                    "export_log",
+                    format!(
+                        "\n📦 Clip {}/{total} (worker {worker}/{max_workers}): {:.1}s - {:.1}s",
+                        index + 1,
+                        clip.start_time,
+                        clip.end_time
+                    ),
+                );
+
+                export_clip(
+                    &app,
+                    &input,
+                    &output_path,
+                    clip.start_time,
+                    clip.duration,
+                    with_subtitles,
+                    target_vmaf,
+                    &tools,
+                )
+                .await
+                .map(|_| (index, output_path))
+            })
+        })
+        .collect();
+
+    // Clips finish out of order across workers; sort by the index each task
+    // carried so exported_files still lines up with the input clip order.
+    let mut indexed_results: Vec<(usize, String)> = Vec::with_capacity(total);
+    for joined in futures::future::join_all(tasks).await {
+        let (index, output_path) = joined.map_err(|e| format!("Export worker panicked: {e}"))??;
+        indexed_results.push((index, output_path));
     }
-    
+    indexed_results.sort_by_key(|(index, _)| *index);
+    let exported_files: Vec<String> = indexed_results.into_iter().map(|(_, path)| path).collect();
+
     let _ = app.emit("export_log", "\n═══════════════════════════════════════════");
     let _ = app.emit("export_log", format!("🎉 Batch export complete! {} clips exported", exported_files.len()));
     let _ = app.emit("export_log", "═══════════════════════════════════════════");
@@ -7,6 +7,7 @@
 //! 
 //! All commands are async and return structured error messages.
 
+use crate::settings::ToolConfigState;
 use crate::video::{ffmpeg, emoji_overlay};
 use std::path::Path;
 
@@ -16,6 +17,8 @@ use std::path::Path;
 /// * `app` - Tauri application handle for event emission
 /// * `input` - Path to input video file
 /// * `output` - Path for output video file
+/// * `target_vmaf` - Optional target VMAF score; when set, the CRF is
+///   auto-selected by probing instead of using the default fixed CRF
 ///
 /// # Returns
 /// * `Ok(String)` - Success message with output path
@@ -31,18 +34,20 @@ pub async fn export_tiktok(
     app: tauri::AppHandle,
     input: String,
     output: String,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
     log::info!("Export request: {input} -> {output}");
-    
+
     // Validate input file exists
     if !Path::new(&input).exists() {
         let err = format!("Input file not found: {input}");
         log::error!("{err}");
         return Err(err);
     }
-    
+
     // Perform conversion
-    match ffmpeg::convert_to_tiktok(&app, &input, &output).await {
+    match ffmpeg::convert_to_tiktok(&app, &input, &output, target_vmaf, &tool_config.snapshot()).await {
         Ok(()) => {
             log::info!("Export completed successfully: {output}");
             Ok(format!("Export complete: {output}"))
@@ -61,6 +66,8 @@ pub async fn export_tiktok(
 /// * `input` - Path to input video file
 /// * `output` - Path for output video file
 /// * `subtitle_file` - Path to subtitle file (.ass or .srt)
+/// * `target_vmaf` - Optional target VMAF score; when set, the CRF is
+///   auto-selected by probing instead of using the default fixed CRF
 ///
 /// # Returns
 /// * `Ok(String)` - Success message with output path
@@ -77,6 +84,8 @@ pub async fn export_with_subs(
     input: String,
     output: String,
     subtitle_file: String,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
     log::info!("Export with subtitles: {input} + {subtitle_file} -> {output}");
     
@@ -95,7 +104,7 @@ pub async fn export_with_subs(
     
     // Use image-based emoji overlay system for guaranteed emoji rendering
     log::info!("Using image-based emoji overlay system");
-    match emoji_overlay::convert_with_emoji_overlays(&app, &input, &output, &subtitle_file).await {
+    match emoji_overlay::convert_with_emoji_overlays(&app, &input, &output, &subtitle_file, target_vmaf, &tool_config.snapshot()).await {
         Ok(()) => {
             log::info!("Export with emoji overlays completed: {output}");
             Ok(format!("Export with emoji overlays complete: {output}"))
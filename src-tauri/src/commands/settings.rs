@@ -0,0 +1,16 @@
+// Tool configuration commands
+use crate::settings::{self, ToolConfig, ToolConfigState};
+
+/// Returns the current tool configuration (explicit binary paths + extra
+/// args), so the settings UI can show what's configured.
+#[tauri::command]
+pub async fn get_tool_config(state: tauri::State<'_, ToolConfigState>) -> Result<ToolConfig, String> {
+    Ok(state.snapshot())
+}
+
+/// Persists a new tool configuration to disk and updates the live state, so
+/// it takes effect on the next download/encode without a restart.
+#[tauri::command]
+pub async fn set_tool_config(app: tauri::AppHandle, config: ToolConfig) -> Result<(), String> {
+    settings::save(&app, config)
+}
@@ -5,7 +5,10 @@
 // Tauri commands for subtitle generation and video overlay.
 // These are the public API exposed to the React frontend.
 
-use crate::subtitle::{self, SubtitleResult};
+use crate::settings::ToolConfigState;
+use crate::subtitle::{
+    self, PromptMode, SubtitleResult, TranscriptSummary, TranslationMode, WhisperBackend,
+};
 
 /// Generate subtitles from video file (NEW clean API)
 ///
@@ -22,9 +25,36 @@ pub async fn create_subtitles(
     app: tauri::AppHandle,
     video_path: String,
     model_size: Option<String>,
+    diarize: Option<bool>,
+    language: Option<String>,
+    prompt_text: Option<String>,
+    prompt_is_transcript: Option<bool>,
+    use_whisper_cpp: Option<bool>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<SubtitleResult, String> {
     let model = model_size.unwrap_or_else(|| "base".to_string());
-    subtitle::generate_subtitles(&app, &video_path, &model).await
+    let prompt_mode = if prompt_is_transcript.unwrap_or(false) {
+        PromptMode::Transcript
+    } else {
+        PromptMode::Hint
+    };
+    let backend = if use_whisper_cpp.unwrap_or(false) {
+        WhisperBackend::WhisperCpp
+    } else {
+        WhisperBackend::Python
+    };
+    subtitle::generate_subtitles(
+        &app,
+        &video_path,
+        &model,
+        diarize.unwrap_or(false),
+        language.as_deref(),
+        prompt_text.as_deref(),
+        prompt_mode,
+        backend,
+        &tool_config.snapshot(),
+    )
+    .await
 }
 
 /// Read subtitle file contents
@@ -42,6 +72,74 @@ pub async fn read_subtitle_file(path: String) -> Result<String, String> {
         .map_err(|e| e.to_string())
 }
 
+/// Resync a subtitle file to the video's audio (fix timing drift)
+///
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `audio_path` - Path to the video or 16kHz mono WAV
+/// * `subtitle_path` - Path to the (possibly drifted) subtitle file
+/// * `output_path` - Path for the realigned subtitle file
+///
+/// # Returns
+/// * `Ok(String)` - Success message
+/// * `Err(String)` - Error message
+#[tauri::command]
+pub async fn resync_subtitles(
+    app: tauri::AppHandle,
+    audio_path: String,
+    subtitle_path: String,
+    output_path: String,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<String, String> {
+    subtitle::resync_subtitles(&app, &audio_path, &subtitle_path, &output_path, &tool_config.snapshot()).await?;
+    Ok(format!("Resynced subtitles saved to: {output_path}"))
+}
+
+/// Summarize a transcript into a caption plus chapter markers
+///
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `subtitle_path` - Path to the transcript subtitle file
+/// * `max_len` - Approximate maximum summary length
+///
+/// # Returns
+/// * `Ok(TranscriptSummary)` - Summary text and chapter points
+/// * `Err(String)` - Error message
+#[tauri::command]
+pub async fn summarize_transcript(
+    app: tauri::AppHandle,
+    subtitle_path: String,
+    max_len: Option<usize>,
+) -> Result<TranscriptSummary, String> {
+    subtitle::summarize_transcript(&app, &subtitle_path, max_len.unwrap_or(200)).await
+}
+
+/// Translate a subtitle file into another language
+///
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `subtitle_path` - Path to the source subtitle file
+/// * `target_lang` - Target language code (e.g. "fr")
+/// * `bilingual` - When true, stack original over translation; else replace
+///
+/// # Returns
+/// * `Ok(String)` - Path to the translated subtitle file
+/// * `Err(String)` - Error message
+#[tauri::command]
+pub async fn translate_subtitles(
+    app: tauri::AppHandle,
+    subtitle_path: String,
+    target_lang: String,
+    bilingual: Option<bool>,
+) -> Result<String, String> {
+    let mode = if bilingual.unwrap_or(false) {
+        TranslationMode::Bilingual
+    } else {
+        TranslationMode::Replace
+    };
+    subtitle::translate_subtitles(&app, &subtitle_path, &target_lang, mode).await
+}
+
 /// Overlay subtitles
 ///
 /// # Arguments
@@ -49,6 +147,7 @@ pub async fn read_subtitle_file(path: String) -> Result<String, String> {
 /// * `video_path` - Path to input video
 /// * `subtitle_path` - Path to subtitle file (.srt or .ass)
 /// * `output_path` - Path for output video
+/// * `target_vmaf` - Optional target VMAF score for the emoji-overlay path
 ///
 /// # Returns
 /// * `Ok(String)` - Success message
@@ -59,8 +158,10 @@ pub async fn overlay_subtitles(
     video_path: String,
     subtitle_path: String,
     output_path: String,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
-    subtitle::overlay_subtitles(&app, &video_path, &subtitle_path, &output_path).await?;
+    subtitle::overlay_subtitles(&app, &video_path, &subtitle_path, &output_path, target_vmaf, &tool_config.snapshot()).await?;
     Ok(format!("Video with subtitles saved to: {}", output_path))
 }
 
@@ -71,6 +172,7 @@ pub async fn overlay_subtitles(
 /// * `video_path` - Path to input video
 /// * `output_path` - Path for output video
 /// * `model_size` - Optional Whisper model size
+/// * `target_vmaf` - Optional target VMAF score for the emoji-overlay path
 ///
 /// # Returns
 /// * `Ok(String)` - Success message
@@ -81,13 +183,27 @@ pub async fn generate_and_overlay_subtitles(
     video_path: String,
     output_path: String,
     model_size: Option<String>,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
     // Step 1: Generate subtitles
     let model = model_size.unwrap_or_else(|| "base".to_string());
-    let result = subtitle::generate_subtitles(&app, &video_path, &model).await?;
+    let tools = tool_config.snapshot();
+    let result = subtitle::generate_subtitles(
+        &app,
+        &video_path,
+        &model,
+        false,
+        None,
+        None,
+        PromptMode::Hint,
+        WhisperBackend::Python,
+        &tools,
+    )
+    .await?;
 
     // Step 2: Overlay onto video
-    subtitle::overlay_subtitles(&app, &video_path, &result.subtitle_path, &output_path).await?;
+    subtitle::overlay_subtitles(&app, &video_path, &result.subtitle_path, &output_path, target_vmaf, &tools).await?;
 
     Ok(format!(
         "Complete! {} segments generated",
@@ -1,5 +1,6 @@
 // Transcription command - generates subtitles from video audio
 use crate::ai::whisper;
+use crate::subtitle;
 use crate::video::ffmpeg;
 
 #[tauri::command]
@@ -30,15 +31,30 @@ pub async fn export_with_auto_subs(
     input: String,
     output: String,
     model_size: Option<String>,
+    translate_to: Option<String>,
 ) -> Result<String, String> {
     // Generate subtitles (returns ASS file path)
     let ass_path = generate_subtitles(app.clone(), input.clone(), model_size).await?;
 
+    // Optionally translate the captions before burn-in, e.g. "download
+    // English video -> export with French captions" for cross-language
+    // repurposing. The translated file keeps the original timestamps and
+    // style fields, so it is burned in below exactly like the untranslated one.
+    let translated_path = if let Some(target_lang) = translate_to.as_deref() {
+        Some(subtitle::translate_ass_for_export(&app, &ass_path, target_lang).await?)
+    } else {
+        None
+    };
+    let burn_path = translated_path.as_deref().unwrap_or(&ass_path);
+
     // Export video with word-by-word subtitles burned in center
-    ffmpeg::convert_with_subtitles(&app, &input, &output, &ass_path).await?;
+    ffmpeg::convert_with_subtitles(&app, &input, &output, burn_path).await?;
 
-    // Clean up ASS file
+    // Clean up ASS file(s)
     let _ = tokio::fs::remove_file(&ass_path).await;
+    if let Some(translated_path) = translated_path {
+        let _ = tokio::fs::remove_file(&translated_path).await;
+    }
 
     Ok(format!("Export complete with word-by-word subtitles: {output}"))
 }
@@ -1,26 +1,90 @@
 // YouTube download commands
-use crate::video::youtube;
+use crate::dependencies::{self, DependencyError, DependencyReport};
+use crate::settings::ToolConfigState;
+use crate::video::{search, youtube};
 use serde::{Deserialize, Serialize};
 
+/// Reports the detected versions and usability of yt-dlp and ffmpeg so the UI
+/// can block downloads until the toolchain is ready.
+#[tauri::command]
+pub async fn check_dependencies() -> Result<DependencyReport, DependencyError> {
+    let report = dependencies::check_dependencies().await;
+    // Surface the first blocking problem as a structured error while still
+    // letting a fully-usable toolchain return the detailed report.
+    if report.all_usable() {
+        Ok(report)
+    } else {
+        Err(dependencies::ensure_dependencies().await.unwrap_err())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoInfo {
     pub title: String,
     pub duration: f64,
     pub uploader: String,
+    pub thumbnail: String,
+    pub view_count: u64,
+    pub upload_date: String,
+    pub formats: Vec<VideoFormat>,
+}
+
+/// A single downloadable format, surfaced to the frontend so users can pick a
+/// quality (1080p vs 720p vs audio-only) before downloading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoFormat {
+    pub format_id: String,
+    pub resolution: String,
+    pub fps: Option<f64>,
+    pub vcodec: String,
+    pub acodec: String,
+    pub filesize: Option<u64>,
+}
+
+impl From<youtube::VideoFormat> for VideoFormat {
+    fn from(f: youtube::VideoFormat) -> Self {
+        Self {
+            format_id: f.format_id,
+            resolution: f.resolution,
+            fps: f.fps,
+            vcodec: f.vcodec,
+            acodec: f.acodec,
+            filesize: f.filesize,
+        }
+    }
+}
+
+impl From<youtube::VideoInfo> for VideoInfo {
+    fn from(info: youtube::VideoInfo) -> Self {
+        Self {
+            title: info.title,
+            duration: info.duration,
+            uploader: info.uploader,
+            thumbnail: info.thumbnail,
+            view_count: info.view_count,
+            upload_date: info.upload_date,
+            formats: info.formats.into_iter().map(Into::into).collect(),
+        }
+    }
 }
 
 #[tauri::command]
 pub async fn download_youtube(
     app: tauri::AppHandle,
     url: String,
+    max_attempts: Option<u32>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
+    dependencies::ensure_dependencies().await.map_err(|e| e.to_string())?;
+
     // Download to a temp directory
     let temp_dir = std::env::temp_dir()
         .join("tiktok-studio-downloads")
         .to_string_lossy()
         .to_string();
 
-    let video_path = youtube::download_youtube_video(&app, &url, &temp_dir).await?;
+    let video_path =
+        youtube::download_youtube_video(&app, &url, &temp_dir, max_attempts, &tool_config.snapshot()).await?;
 
     Ok(video_path)
 }
@@ -29,14 +93,55 @@ pub async fn download_youtube(
 pub async fn get_youtube_info(
     app: tauri::AppHandle,
     url: String,
+    max_attempts: Option<u32>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<VideoInfo, String> {
-    let info = youtube::get_video_info(&app, &url).await?;
+    let info = youtube::get_video_info(&app, &url, max_attempts, &tool_config.snapshot()).await?;
+
+    Ok(info.into())
+}
+
+/// Returns the full list of downloadable formats for a `YouTube` URL so the
+/// frontend can present a quality picker.
+#[tauri::command]
+pub async fn get_youtube_formats(
+    app: tauri::AppHandle,
+    url: String,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<Vec<VideoFormat>, String> {
+    let info = youtube::get_video_info(&app, &url, None, &tool_config.snapshot()).await?;
+
+    Ok(info.formats.into_iter().map(Into::into).collect())
+}
+
+/// Downloads a video using a user-selected `format_id` instead of the default
+/// "best" selector.
+#[tauri::command]
+pub async fn download_youtube_with_format(
+    app: tauri::AppHandle,
+    url: String,
+    format_id: String,
+    max_attempts: Option<u32>,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<String, String> {
+    dependencies::ensure_dependencies().await.map_err(|e| e.to_string())?;
+
+    let temp_dir = std::env::temp_dir()
+        .join("tiktok-studio-downloads")
+        .to_string_lossy()
+        .to_string();
 
-    Ok(VideoInfo {
-        title: info.title,
-        duration: info.duration,
-        uploader: info.uploader,
-    })
+    let video_path = youtube::download_youtube_video_with_format(
+        &app,
+        &url,
+        &temp_dir,
+        &format_id,
+        max_attempts,
+        &tool_config.snapshot(),
+    )
+    .await?;
+
+    Ok(video_path)
 }
 
 #[tauri::command]
@@ -45,9 +150,18 @@ pub async fn download_and_export(
     url: String,
     output: String,
     with_subtitles: Option<bool>,
+    target_vmaf: Option<f64>,
+    tool_config: tauri::State<'_, ToolConfigState>,
 ) -> Result<String, String> {
+    dependencies::ensure_dependencies().await.map_err(|e| e.to_string())?;
+    let tools = tool_config.snapshot();
+
     // Step 1: Download from YouTube
-    let video_path = download_youtube(app.clone(), url).await?;
+    let temp_dir = std::env::temp_dir()
+        .join("tiktok-studio-downloads")
+        .to_string_lossy()
+        .to_string();
+    let video_path = youtube::download_youtube_video(&app, &url, &temp_dir, None, &tools).await?;
 
     // Step 2: Export to TikTok format
     if with_subtitles.unwrap_or(false) {
@@ -56,10 +170,11 @@ pub async fn download_and_export(
             video_path.clone(),
             output,
             Some("base".to_string()),
+            None,
         )
         .await?;
     } else {
-        crate::video::ffmpeg::convert_to_tiktok(&app, &video_path, &output).await?;
+        crate::video::ffmpeg::convert_to_tiktok(&app, &video_path, &output, target_vmaf, &tools).await?;
     }
 
     // Step 3: Clean up downloaded file
@@ -67,3 +182,45 @@ pub async fn download_and_export(
 
     Ok("YouTube video processed successfully".to_string())
 }
+
+/// Downloads a whole playlist or channel into a temp directory, returning the
+/// downloaded file paths so they can be queued through the export flow.
+#[tauri::command]
+pub async fn download_youtube_playlist(
+    app: tauri::AppHandle,
+    url: String,
+    max_count: Option<usize>,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<Vec<String>, String> {
+    dependencies::ensure_dependencies().await.map_err(|e| e.to_string())?;
+
+    let temp_dir = std::env::temp_dir()
+        .join("tiktok-studio-downloads")
+        .to_string_lossy()
+        .to_string();
+
+    youtube::download_youtube_playlist(&app, &url, &temp_dir, max_count, &tool_config.snapshot()).await
+}
+
+/// Searches YouTube (via Invidious) so users can find clips inside the app.
+#[tauri::command]
+pub async fn search_youtube(
+    app: tauri::AppHandle,
+    query: String,
+) -> Result<Vec<search::SearchResult>, String> {
+    search::search_youtube(&app, &query).await
+}
+
+/// Downloads and exports a search result by its video id, reusing the existing
+/// download/export pipeline via the constructed watch URL.
+#[tauri::command]
+pub async fn search_and_export(
+    app: tauri::AppHandle,
+    video_id: String,
+    output: String,
+    with_subtitles: Option<bool>,
+    tool_config: tauri::State<'_, ToolConfigState>,
+) -> Result<String, String> {
+    let url = format!("https://www.youtube.com/watch?v={video_id}");
+    download_and_export(app, url, output, with_subtitles, None, tool_config).await
+}
@@ -0,0 +1,156 @@
+// ============================================================================
+// Dependency Management Module
+// ============================================================================
+//
+// Verifies that the external toolchain the app shells out to (yt-dlp and
+// ffmpeg) is present and recent enough. The check runs at startup and before
+// each download so the UI can block download actions until the toolchain is
+// usable instead of failing mid-run.
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+/// Minimum known-good yt-dlp version, baked into the crate. yt-dlp uses a
+/// date-based `YYYY.MM.DD` scheme.
+const MIN_YTDLP_VERSION: (u32, u32, u32) = (2023, 7, 6);
+
+/// Why a dependency check failed, in enough detail for the frontend to render
+/// install/upgrade instructions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DependencyError {
+    /// The tool is not on PATH at all.
+    NotInstalled { name: String },
+    /// The tool is present but older than the baked-in baseline.
+    Outdated {
+        name: String,
+        found: String,
+        required: String,
+    },
+}
+
+impl std::fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotInstalled { name } => write!(f, "{name} is not installed"),
+            Self::Outdated { name, found, required } => {
+                write!(f, "{name} {found} is older than the required {required}")
+            }
+        }
+    }
+}
+
+/// Status of a single dependency, returned to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub installed: bool,
+    pub version: Option<String>,
+    pub usable: bool,
+}
+
+/// Aggregate toolchain report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyReport {
+    pub ytdlp: DependencyStatus,
+    pub ffmpeg: DependencyStatus,
+}
+
+impl DependencyReport {
+    /// True when every dependency is present and recent enough to run.
+    #[must_use]
+    pub const fn all_usable(&self) -> bool {
+        self.ytdlp.usable && self.ffmpeg.usable
+    }
+}
+
+/// Probes both tools and builds a [`DependencyReport`].
+pub async fn check_dependencies() -> DependencyReport {
+    DependencyReport {
+        ytdlp: check_ytdlp().await,
+        ffmpeg: check_ffmpeg().await,
+    }
+}
+
+/// Verifies the toolchain, returning a structured error on the first problem
+/// so callers can gate download actions.
+pub async fn ensure_dependencies() -> Result<(), DependencyError> {
+    let report = check_dependencies().await;
+
+    if !report.ytdlp.installed {
+        return Err(DependencyError::NotInstalled { name: "yt-dlp".to_string() });
+    }
+    if !report.ytdlp.usable {
+        return Err(DependencyError::Outdated {
+            name: "yt-dlp".to_string(),
+            found: report.ytdlp.version.unwrap_or_default(),
+            required: format_version(MIN_YTDLP_VERSION),
+        });
+    }
+    if !report.ffmpeg.installed {
+        return Err(DependencyError::NotInstalled { name: "ffmpeg".to_string() });
+    }
+
+    Ok(())
+}
+
+/// Detects yt-dlp and compares its version against the baseline.
+async fn check_ytdlp() -> DependencyStatus {
+    let version = run_version(&["yt-dlp", "--version"]).await;
+
+    let usable = version
+        .as_deref()
+        .and_then(parse_ytdlp_version)
+        .is_some_and(|v| v >= MIN_YTDLP_VERSION);
+
+    DependencyStatus {
+        name: "yt-dlp".to_string(),
+        installed: version.is_some(),
+        version,
+        usable,
+    }
+}
+
+/// Detects ffmpeg (any version is accepted).
+async fn check_ffmpeg() -> DependencyStatus {
+    let version = run_version(&["ffmpeg", "-version"]).await.map(|out| {
+        // ffmpeg prints "ffmpeg version 6.0 ..." on the first line.
+        out.lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(2))
+            .unwrap_or(&out)
+            .to_string()
+    });
+
+    DependencyStatus {
+        name: "ffmpeg".to_string(),
+        installed: version.is_some(),
+        version: version.clone(),
+        usable: version.is_some(),
+    }
+}
+
+/// Runs `program --version` and returns the trimmed stdout, or `None` if the
+/// program is not on PATH / exits non-zero.
+async fn run_version(args: &[&str]) -> Option<String> {
+    let output = Command::new(args[0]).args(&args[1..]).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Parses a `YYYY.MM.DD` yt-dlp version string into a comparable tuple.
+fn parse_ytdlp_version(raw: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = raw.trim().split('.').map(str::parse::<u32>);
+    let year = parts.next()?.ok()?;
+    let month = parts.next()?.ok()?;
+    let day = parts.next().and_then(Result::ok).unwrap_or(0);
+    Some((year, month, day))
+}
+
+/// Formats a baseline version tuple for display.
+fn format_version((y, m, d): (u32, u32, u32)) -> String {
+    format!("{y}.{m:02}.{d:02}")
+}
@@ -16,12 +16,16 @@
 // Module declarations
 mod ai;
 mod commands;
+mod dependencies;
+mod settings;
 mod subtitle;
 mod video;
 
 // Import commands
 use commands::subtitle as subtitle_cmd;
-use commands::{clips, export, transcribe, youtube};
+use commands::{clips, export, settings as settings_cmd, transcribe, youtube};
+use settings::ToolConfigState;
+use tauri::Manager;
 
 /// Application version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -42,6 +46,21 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            // Load the persisted tool config (explicit binary paths + extra
+            // args) before anything tries to shell out.
+            app.manage(ToolConfigState::load(&app.handle().clone()));
+
+            // Probe the external toolchain once at startup and log the result;
+            // each download command runs its own check again.
+            tauri::async_runtime::spawn(async {
+                match dependencies::ensure_dependencies().await {
+                    Ok(()) => log::info!("Toolchain OK (yt-dlp + ffmpeg)"),
+                    Err(e) => log::warn!("Toolchain check: {e}"),
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             export::export_tiktok,
             export::export_with_subs,
@@ -50,13 +69,24 @@ pub fn run() {
             clips::detect_clips,
             clips::extract_and_export_clip,
             clips::batch_export_clips,
+            youtube::check_dependencies,
             youtube::download_youtube,
             youtube::get_youtube_info,
+            youtube::get_youtube_formats,
+            youtube::download_youtube_with_format,
             youtube::download_and_export,
+            youtube::download_youtube_playlist,
+            youtube::search_youtube,
+            youtube::search_and_export,
             subtitle_cmd::create_subtitles,
+            subtitle_cmd::resync_subtitles,
+            subtitle_cmd::summarize_transcript,
+            subtitle_cmd::translate_subtitles,
             subtitle_cmd::overlay_subtitles,
             subtitle_cmd::generate_and_overlay_subtitles,
             subtitle_cmd::read_subtitle_file,
+            settings_cmd::get_tool_config,
+            settings_cmd::set_tool_config,
         ])
         .run(tauri::generate_context!())
         .unwrap_or_else(|err| {
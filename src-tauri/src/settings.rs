@@ -0,0 +1,105 @@
+// ============================================================================
+// Tool Configuration
+// ============================================================================
+//
+// Packaged macOS/Windows builds frequently can't find `ffmpeg`, `ffprobe`,
+// `python3`, or `yt-dlp` on PATH, and power users often want to pass extra
+// flags (e.g. a yt-dlp cookies file, an ffmpeg hwaccel) to every invocation.
+// This holds the user-configurable binary locations and extra arguments,
+// persisted to a small JSON file in the app's config directory and mirrored
+// into Tauri-managed state so commands can read it without touching disk.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::Manager;
+
+const CONFIG_FILE_NAME: &str = "tool_config.json";
+
+/// Explicit paths to the external binaries the app shells out to, plus any
+/// extra arguments appended to yt-dlp downloads and to the clip-extraction,
+/// `TikTok`-conversion and emoji-overlay encodes (the plain subtitle burn-in
+/// encodes do not use them). An unset path falls back to the bare command
+/// name, relying on PATH resolution (today's behavior).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolConfig {
+    pub ffmpeg_path: Option<String>,
+    pub ffprobe_path: Option<String>,
+    pub python_path: Option<String>,
+    pub ytdlp_path: Option<String>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+impl ToolConfig {
+    /// Resolves the configured `ffmpeg` binary, falling back to the bare name.
+    #[must_use]
+    pub fn ffmpeg(&self) -> &str {
+        self.ffmpeg_path.as_deref().unwrap_or("ffmpeg")
+    }
+
+    /// Resolves the configured `ffprobe` binary, falling back to the bare name.
+    #[must_use]
+    pub fn ffprobe(&self) -> &str {
+        self.ffprobe_path.as_deref().unwrap_or("ffprobe")
+    }
+
+    /// Resolves the configured `python3` binary, falling back to the bare name.
+    #[must_use]
+    pub fn python(&self) -> &str {
+        self.python_path.as_deref().unwrap_or("python3")
+    }
+
+    /// Resolves the configured `yt-dlp` binary, falling back to the bare name.
+    #[must_use]
+    pub fn ytdlp(&self) -> &str {
+        self.ytdlp_path.as_deref().unwrap_or("yt-dlp")
+    }
+}
+
+/// Tauri-managed state wrapping the current [`ToolConfig`].
+pub struct ToolConfigState(pub Mutex<ToolConfig>);
+
+impl ToolConfigState {
+    /// Loads the persisted config (or defaults) for use at app startup.
+    #[must_use]
+    pub fn load(app: &tauri::AppHandle) -> Self {
+        Self(Mutex::new(load(app)))
+    }
+
+    /// Returns a clone of the current config.
+    #[must_use]
+    pub fn snapshot(&self) -> ToolConfig {
+        self.0.lock().expect("tool config mutex poisoned").clone()
+    }
+}
+
+fn config_file_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(CONFIG_FILE_NAME))
+}
+
+/// Loads the persisted config from disk, defaulting to bare tool names (the
+/// pre-existing behavior) when no config file exists yet or it fails to parse.
+#[must_use]
+pub fn load(app: &tauri::AppHandle) -> ToolConfig {
+    config_file_path(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Persists `config` to disk and updates the in-memory managed state.
+pub fn save(app: &tauri::AppHandle, config: ToolConfig) -> Result<(), String> {
+    let path = config_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let raw = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, raw).map_err(|e| e.to_string())?;
+
+    let state = app.state::<ToolConfigState>();
+    *state.0.lock().expect("tool config mutex poisoned") = config;
+    Ok(())
+}
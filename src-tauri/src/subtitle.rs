@@ -16,6 +16,7 @@
 //   let result = generate_subtitles(&app, "video.mp4", "base").await?;
 //   println!("Subtitles: {}", result.subtitle_path);
 
+use crate::settings::ToolConfig;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tauri::Emitter;
@@ -38,18 +39,24 @@ pub struct SubtitleResult {
 }
 
 /// Individual subtitle segment with timing
+///
+/// Diarization does not populate this struct — it's a post-write text pass
+/// over the rendered ASS/SRT cues (see [`tag_ass_speakers`] and
+/// [`tag_srt_speakers`]), which prefix each cue with its speaker label
+/// directly in the file content rather than threading it through a segment
+/// object.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct SubtitleSegment {
     /// Start time in milliseconds
     pub start_time_ms: u64,
-    
+
     /// End time in milliseconds
     pub end_time_ms: u64,
-    
+
     /// Subtitle text
     pub text: String,
-    
+
     /// Confidence score (0.0 - 1.0)
     pub confidence: f32,
 }
@@ -74,83 +81,250 @@ pub async fn generate_subtitles(
     app: &tauri::AppHandle,
     video_path: &str,
     model_size: &str,
+    diarize: bool,
+    language: Option<&str>,
+    prompt_text: Option<&str>,
+    prompt_mode: PromptMode,
+    backend: WhisperBackend,
+    tools: &ToolConfig,
 ) -> Result<SubtitleResult, String> {
     log::info!("Starting subtitle generation: {video_path}");
     let _ = app.emit("subtitle_progress", "Starting subtitle generation...");
-    
+
     // Validate input file
     if !Path::new(video_path).exists() {
         return Err(format!("Video file not found: {video_path}"));
     }
-    
+
+    // Probe the input up-front for stream types and duration.
+    let media = probe_media(video_path, tools).await.unwrap_or_default();
+    if media.subtitle_streams > 0 {
+        let _ = app.emit(
+            "subtitle_progress",
+            format!("ℹ️ Input has {} embedded subtitle stream(s) — they can be extracted instead of re-transcribed", media.subtitle_streams),
+        );
+    }
+
     // Step 1: Extract audio from video
     let _ = app.emit("subtitle_progress", "Extracting audio...");
-    let audio_path = extract_audio_for_transcription(app, video_path).await?;
-    
+    let audio_path =
+        extract_audio_for_transcription(app, video_path, media.has_video, tools).await?;
+
     // Step 2: Run Whisper transcription
     let _ = app.emit("subtitle_progress", "Transcribing with AI...");
-    let subtitle_path = run_whisper_transcription(app, &audio_path, model_size).await?;
-    
-    // Step 3: Parse and validate output
+    let subtitle_path =
+        run_whisper_transcription(app, &audio_path, model_size, language, prompt_text, prompt_mode, backend)
+            .await?;
+
+    // Step 3: Optional speaker diarization (interview/podcast clips)
+    if diarize {
+        let _ = app.emit("subtitle_progress", "Identifying speakers...");
+        if let Err(e) = diarize_subtitles(app, &audio_path, &subtitle_path).await {
+            // Diarization is best-effort: keep the captions if it fails.
+            log::warn!("Diarization failed, keeping single-track captions: {e}");
+        }
+    }
+
+    // Step 4: Parse and validate output
     let _ = app.emit("subtitle_progress", "Validating subtitles...");
-    let result = parse_subtitle_file(&subtitle_path)?;
-    
+    let result = parse_subtitle_file(&subtitle_path, language, media.duration_seconds)?;
+
     // Cleanup temporary audio file
     let _ = tokio::fs::remove_file(&audio_path).await;
-    
+
     log::info!("Subtitle generation complete: {} segments", result.segment_count);
     let _ = app.emit("subtitle_progress", "Complete!");
-    
+
     Ok(result)
 }
 
-/// Extract audio from video in Whisper-compatible format
+/// Stream/container facts gathered from ffprobe before processing.
+#[derive(Debug, Clone, Default)]
+pub struct MediaInfo {
+    /// Container duration in seconds (0.0 if unknown).
+    pub duration_seconds: f64,
+    /// Whether the input carries at least one video stream.
+    pub has_video: bool,
+    /// Number of embedded subtitle streams.
+    pub subtitle_streams: usize,
+}
+
+/// Probes an input with ffprobe for stream types and container duration.
+async fn probe_media(path: &str, tools: &ToolConfig) -> Result<MediaInfo, String> {
+    let output = Command::new(tools.ffprobe())
+        .args([
+            "-v", "error",
+            "-show_entries", "format=duration:stream=codec_type",
+            "-of", "json",
+            path,
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffprobe: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {e}"))?;
+
+    let mut info = MediaInfo {
+        duration_seconds: json["format"]["duration"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0.0),
+        ..Default::default()
+    };
+
+    if let Some(streams) = json["streams"].as_array() {
+        for stream in streams {
+            match stream["codec_type"].as_str() {
+                Some("video") => info.has_video = true,
+                Some("subtitle") => info.subtitle_streams += 1,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(info)
+}
+
+/// Extract audio from video in Whisper-compatible format.
+///
+/// Skips the `-vn` video-drop path for audio-only inputs and derives the
+/// output stem from the path (so `.mov/.mkv/.webm` containers work, not just
+/// `.mp4`).
 async fn extract_audio_for_transcription(
     app: &tauri::AppHandle,
     video_path: &str,
+    has_video: bool,
+    tools: &ToolConfig,
 ) -> Result<String, String> {
-    let audio_path = format!("{}_audio.wav", video_path.replace(".mp4", ""));
-    
-    let args = vec![
-        "-y",
-        "-i", video_path,
-        "-vn",                  // No video
+    let stem = std::path::Path::new(video_path)
+        .with_extension("")
+        .to_string_lossy()
+        .to_string();
+    let audio_path = format!("{stem}_audio.wav");
+
+    let mut args = vec!["-y", "-i", video_path];
+    if has_video {
+        args.push("-vn"); // Drop video only when there is one.
+    }
+    args.extend([
         "-acodec", "pcm_s16le", // 16-bit PCM
         "-ar", "16000",         // 16kHz sample rate (Whisper default)
         "-ac", "1",             // Mono
         &audio_path,
-    ];
-    
-    let output = Command::new("ffmpeg")
+    ]);
+
+    let output = Command::new(tools.ffmpeg())
         .args(&args)
         .output()
         .await
         .map_err(|e| format!("Failed to extract audio: {e}"))?;
-    
+
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("FFmpeg audio extraction failed: {stderr}"));
     }
-    
+
     let _ = app.emit("subtitle_progress", "Audio extracted");
     Ok(audio_path)
 }
 
-/// Run Whisper transcription using Python script
+/// How `prompt_text` is fed to Whisper.
+///
+/// Whisper's `initial_prompt` can be used two ways: a loosely-related hint that
+/// nudges vocabulary/spelling, or the expected transcript itself (useful for
+/// lyrics where the text is known up front).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptMode {
+    /// Loosely-related text that biases spelling of names/jargon.
+    Hint,
+    /// The expected transcript (e.g. known lyrics).
+    Transcript,
+}
+
+impl PromptMode {
+    /// CLI token mirrored into `whisper_transcribe.py`.
+    const fn as_arg(self) -> &'static str {
+        match self {
+            Self::Hint => "hint",
+            Self::Transcript => "transcript",
+        }
+    }
+}
+
+/// Transcription engine to use.
+///
+/// `Python` drives the `whisper_transcribe.py` script (heavy torch stack);
+/// `WhisperCpp` invokes a bundled `whisper.cpp` CLI with a downloaded ggml
+/// model, which is far easier to ship in a Tauri desktop build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WhisperBackend {
+    #[default]
+    Python,
+    WhisperCpp,
+}
+
+/// Dispatches transcription to the selected backend.
 async fn run_whisper_transcription(
     app: &tauri::AppHandle,
     audio_path: &str,
     model_size: &str,
+    language: Option<&str>,
+    prompt_text: Option<&str>,
+    prompt_mode: PromptMode,
+    backend: WhisperBackend,
+) -> Result<String, String> {
+    match backend {
+        WhisperBackend::Python => {
+            run_whisper_python(app, audio_path, model_size, language, prompt_text, prompt_mode).await
+        }
+        WhisperBackend::WhisperCpp => {
+            run_whisper_cpp(app, audio_path, model_size, language, prompt_text).await
+        }
+    }
+}
+
+/// Run Whisper transcription using Python script
+async fn run_whisper_python(
+    app: &tauri::AppHandle,
+    audio_path: &str,
+    model_size: &str,
+    language: Option<&str>,
+    prompt_text: Option<&str>,
+    prompt_mode: PromptMode,
 ) -> Result<String, String> {
     let output_path = audio_path.replace(".wav", ".srt");
-    
+
+    let mut args: Vec<String> = vec![
+        "whisper_transcribe.py".to_string(),
+        audio_path.to_string(),
+        output_path.clone(),
+        model_size.to_string(),
+    ];
+
+    // Force the decode language instead of auto-detect (helps short/noisy clips).
+    if let Some(lang) = language {
+        args.push("--language".to_string());
+        args.push(lang.to_string());
+    }
+
+    // Bias decoding with related text or the known transcript.
+    if let Some(prompt) = prompt_text {
+        args.push("--prompt".to_string());
+        args.push(prompt.to_string());
+        args.push("--prompt-mode".to_string());
+        args.push(prompt_mode.as_arg().to_string());
+    }
+
     let output = Command::new("python3")
-        .args([
-            "whisper_transcribe.py",
-            audio_path,
-            &output_path,
-            model_size,
-        ])
+        .args(&args)
         .output()
         .await
         .map_err(|e| format!("Failed to run Whisper: {e}"))?;
@@ -173,28 +347,882 @@ async fn run_whisper_transcription(
     }
 }
 
+/// Run transcription with the native `whisper.cpp` CLI.
+///
+/// Ensures the requested ggml model is cached, runs the CLI to emit SRT (with
+/// word timestamps), and returns the SRT path normalized into the same shape
+/// the rest of the pipeline expects.
+async fn run_whisper_cpp(
+    app: &tauri::AppHandle,
+    audio_path: &str,
+    model_size: &str,
+    language: Option<&str>,
+    prompt_text: Option<&str>,
+) -> Result<String, String> {
+    let model_path = ensure_ggml_model(app, model_size).await?;
+
+    // whisper.cpp writes `<of>.srt`; strip our `.wav` to build the base.
+    let output_base = audio_path.trim_end_matches(".wav").to_string();
+    let output_path = format!("{output_base}.srt");
+
+    let mut args: Vec<String> = vec![
+        "-m".to_string(), model_path,
+        "-f".to_string(), audio_path.to_string(),
+        "-osrt".to_string(),
+        "-ml".to_string(), "1".to_string(), // word-level timestamps
+        "-of".to_string(), output_base,
+    ];
+
+    if let Some(lang) = language {
+        args.push("-l".to_string());
+        args.push(lang.to_string());
+    }
+    if let Some(prompt) = prompt_text {
+        args.push("--prompt".to_string());
+        args.push(prompt.to_string());
+    }
+
+    let output = Command::new("whisper-cli")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run whisper.cpp: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("whisper.cpp transcription failed: {stderr}"));
+    }
+
+    if Path::new(&output_path).exists() {
+        let _ = app.emit("subtitle_progress", "Transcription complete (whisper.cpp)");
+        Ok(output_path)
+    } else {
+        Err("No subtitle file generated".to_string())
+    }
+}
+
+/// Maps a friendly model size ("tiny".."large") to its ggml filename.
+fn ggml_model_filename(model_size: &str) -> String {
+    let size = match model_size {
+        "tiny" | "base" | "small" | "medium" => model_size,
+        "large" => "large-v3",
+        other => other,
+    };
+    format!("ggml-{size}.bin")
+}
+
+/// Ensures the requested ggml model is present in the local cache, downloading
+/// it from the whisper.cpp model host on first use.
+async fn ensure_ggml_model(app: &tauri::AppHandle, model_size: &str) -> Result<String, String> {
+    let filename = ggml_model_filename(model_size);
+    let model_path = format!("models/{filename}");
+
+    if Path::new(&model_path).exists() {
+        return Ok(model_path);
+    }
+
+    let _ = app.emit(
+        "subtitle_progress",
+        format!("📦 Downloading {filename} (first use)..."),
+    );
+
+    std::fs::create_dir_all("models").map_err(|e| format!("Failed to create models dir: {e}"))?;
+
+    let url = format!("https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{filename}");
+    let output = Command::new("curl")
+        .args(["-L", "-o", &model_path, &url])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to download model: {e}"))?;
+
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&model_path).await;
+        return Err(format!(
+            "Failed to download ggml model: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let _ = app.emit("subtitle_progress", "✓ Model ready");
+    Ok(model_path)
+}
+
 /// Parse subtitle file and extract metadata
-fn parse_subtitle_file(subtitle_path: &str) -> Result<SubtitleResult, String> {
+///
+/// `forced_language` is the language the caller pinned (if any); otherwise the
+/// language auto-detected by Whisper is read from the `<output>.lang` sidecar
+/// the script writes, falling back to `"auto"`.
+fn parse_subtitle_file(
+    subtitle_path: &str,
+    forced_language: Option<&str>,
+    container_duration: f64,
+) -> Result<SubtitleResult, String> {
     let content = std::fs::read_to_string(subtitle_path)
         .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
-    
+
     let path = std::path::Path::new(subtitle_path);
-    
+
     // Count segments (simple heuristic) - using case-insensitive comparison
     let segment_count = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("srt")) {
         content.lines().filter(|line| line.contains("-->")).count()
     } else {
         content.lines().filter(|line| line.starts_with("Dialogue:")).count()
     };
-    
+
+    // Prefer the container duration; fall back to the last subtitle timestamp.
+    let duration_seconds = if container_duration > 0.0 {
+        container_duration
+    } else {
+        subtitle_intervals(&content)
+            .iter()
+            .map(|&(_, end)| end)
+            .max()
+            .map_or(0.0, |ms| ms as f64 / 1000.0)
+    };
+
+    // Report the actual language: the forced one, or Whisper's detection.
+    let language = forced_language.map(str::to_string).unwrap_or_else(|| {
+        std::fs::read_to_string(format!("{subtitle_path}.lang"))
+            .map(|s| s.trim().to_string())
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "auto".to_string())
+    });
+
     Ok(SubtitleResult {
         subtitle_path: subtitle_path.to_string(),
         segment_count,
-        duration_seconds: 0.0, // TODO: Calculate from last timestamp
-        language: "auto".to_string(),
+        duration_seconds,
+        language,
     })
 }
 
+/// A diarized speaker turn in milliseconds.
+struct SpeakerTurn {
+    start_ms: u64,
+    end_ms: u64,
+    speaker: String,
+}
+
+/// Runs the diarization pass and rewrites the subtitle file so each
+/// `Dialogue:` line is tagged with the overlapping speaker.
+async fn diarize_subtitles(
+    app: &tauri::AppHandle,
+    audio_path: &str,
+    subtitle_path: &str,
+) -> Result<(), String> {
+    let turns = run_diarization(app, audio_path).await?;
+    if turns.is_empty() {
+        return Ok(());
+    }
+
+    let is_ass = std::path::Path::new(subtitle_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ass"));
+
+    let content = std::fs::read_to_string(subtitle_path)
+        .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
+
+    // The Python backend returns ASS when it wrote one and SRT otherwise, and
+    // whisper.cpp returns SRT, so both formats need tagging.
+    let rewritten = if is_ass {
+        tag_ass_speakers(&content, &turns)
+    } else {
+        tag_srt_speakers(&content, &turns)
+    };
+    std::fs::write(subtitle_path, rewritten)
+        .map_err(|e| format!("Failed to write diarized subtitles: {e}"))?;
+
+    let _ = app.emit("subtitle_progress", "Speakers identified");
+    Ok(())
+}
+
+/// Produces speaker turns for the audio via the `diarize.py` sidecar, which
+/// emits a JSON array of `{start, end, speaker}` (seconds).
+async fn run_diarization(
+    app: &tauri::AppHandle,
+    audio_path: &str,
+) -> Result<Vec<SpeakerTurn>, String> {
+    let output = Command::new("python3")
+        .args(["diarize.py", audio_path])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run diarization: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse diarization output: {e}"))?;
+
+    let turns = json
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|t| {
+                    Some(SpeakerTurn {
+                        start_ms: (t["start"].as_f64()? * 1000.0) as u64,
+                        end_ms: (t["end"].as_f64()? * 1000.0) as u64,
+                        speaker: t["speaker"].as_str().unwrap_or("S1").to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let _ = app.emit("subtitle_progress", format!("Found {} speaker turns", turns.len()));
+    Ok(turns)
+}
+
+/// Rewrites ASS `Dialogue:` lines, prefixing each with the label of the
+/// speaker whose turn overlaps it the most (falling back to the nearest turn).
+fn tag_ass_speakers(content: &str, turns: &[SpeakerTurn]) -> String {
+    content
+        .lines()
+        .map(|line| {
+            let Some(rest) = line.strip_prefix("Dialogue:") else {
+                return line.to_string();
+            };
+
+            // Dialogue fields: Layer,Start,End,Style,...,Text (9 commas before text)
+            let fields: Vec<&str> = rest.splitn(10, ',').collect();
+            if fields.len() < 10 {
+                return line.to_string();
+            }
+
+            let start_ms = parse_ass_time(fields[1].trim());
+            let end_ms = parse_ass_time(fields[2].trim());
+            let speaker = best_speaker(start_ms, end_ms, turns);
+
+            let mut head = fields[..9].join(",");
+            head.push(',');
+            format!("Dialogue:{head}[{speaker}] {}", fields[9])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rewrites SRT cue text, prefixing each cue's first text line with the
+/// label of the speaker whose turn overlaps it the most. Mirrors
+/// [`tag_ass_speakers`] for the SRT format, which whisper.cpp always produces
+/// and the Python backend produces when it writes no ASS file.
+fn tag_srt_speakers(content: &str, turns: &[SpeakerTurn]) -> String {
+    let mut out = Vec::new();
+    let mut lines = content.lines().peekable();
+    let mut pending_speaker: Option<String> = None;
+
+    while let Some(line) = lines.next() {
+        if let Some((start, end)) = line.split_once("-->") {
+            pending_speaker = Some(best_speaker(
+                parse_srt_time(start.trim()),
+                parse_srt_time(end.trim()),
+                turns,
+            ));
+            out.push(line.to_string());
+            continue;
+        }
+
+        if let Some(speaker) = pending_speaker.take() {
+            if !line.trim().is_empty() {
+                out.push(format!("[{speaker}] {line}"));
+                continue;
+            }
+        }
+        out.push(line.to_string());
+    }
+
+    out.join("\n")
+}
+
+/// Picks the speaker with the maximum temporal overlap with `[start, end)`,
+/// defaulting to the nearest turn when nothing overlaps.
+fn best_speaker(start_ms: u64, end_ms: u64, turns: &[SpeakerTurn]) -> String {
+    let mut best = (&turns[0].speaker, 0_u64);
+    let mut nearest = (&turns[0].speaker, u64::MAX);
+
+    for turn in turns {
+        let overlap = end_ms.min(turn.end_ms).saturating_sub(start_ms.max(turn.start_ms));
+        if overlap > best.1 {
+            best = (&turn.speaker, overlap);
+        }
+
+        let mid = (start_ms + end_ms) / 2;
+        let turn_mid = (turn.start_ms + turn.end_ms) / 2;
+        let distance = mid.abs_diff(turn_mid);
+        if distance < nearest.1 {
+            nearest = (&turn.speaker, distance);
+        }
+    }
+
+    if best.1 > 0 { best.0.clone() } else { nearest.0.clone() }
+}
+
+/// Parses an ASS timestamp (`H:MM:SS.cs`) into milliseconds.
+fn parse_ass_time(ts: &str) -> u64 {
+    let parts: Vec<&str> = ts.split(':').collect();
+    if parts.len() != 3 {
+        return 0;
+    }
+    let hours: u64 = parts[0].parse().unwrap_or(0);
+    let minutes: u64 = parts[1].parse().unwrap_or(0);
+    let (secs, cs) = parts[2].split_once('.').unwrap_or((parts[2], "0"));
+    let seconds: u64 = secs.parse().unwrap_or(0);
+    let centis: u64 = cs.parse().unwrap_or(0);
+    ((hours * 3600 + minutes * 60 + seconds) * 1000) + centis * 10
+}
+
+/// A timestamped chapter marker derived from transcript pauses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    /// Chapter start time in milliseconds.
+    pub start_ms: u64,
+    /// Short title (first salient line of the block).
+    pub title: String,
+}
+
+/// Result of summarizing a transcript: a short summary plus chapter markers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSummary {
+    /// Concise plain-text summary of the whole transcript.
+    pub summary: String,
+    /// Optional timestamped chapter points.
+    pub chapters: Vec<Chapter>,
+}
+
+/// Minimum pause (milliseconds) between cues that starts a new chapter block.
+const CHAPTER_GAP_MS: u64 = 2_000;
+
+/// Summarizes a transcript into a short description plus chapter markers,
+/// useful for auto-writing a TikTok caption from a clip's speech.
+pub async fn summarize_transcript(
+    app: &tauri::AppHandle,
+    subtitle_path: &str,
+    max_len: usize,
+) -> Result<TranscriptSummary, String> {
+    let _ = app.emit("subtitle_progress", "Summarizing transcript...");
+
+    let content = std::fs::read_to_string(subtitle_path)
+        .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
+
+    let segments = parse_timed_segments(&content);
+    if segments.is_empty() {
+        return Err("No subtitle cues found to summarize".to_string());
+    }
+
+    // Group consecutive segments into topical blocks on long pauses, taking the
+    // first line of each block as the chapter title.
+    let mut chapters = Vec::new();
+    let mut block_start: Option<(u64, String)> = None;
+    let mut prev_end = 0_u64;
+    for (start, end, text) in &segments {
+        let gap = start.saturating_sub(prev_end);
+        if block_start.is_none() || gap > CHAPTER_GAP_MS {
+            if let Some((bs, title)) = block_start.take() {
+                chapters.push(Chapter { start_ms: bs, title });
+            }
+            block_start = Some((*start, text.trim().to_string()));
+        }
+        prev_end = *end;
+    }
+    if let Some((bs, title)) = block_start {
+        chapters.push(Chapter { start_ms: bs, title });
+    }
+
+    // Concatenate the transcript in order and summarize via the sidecar.
+    let full_text = segments
+        .iter()
+        .map(|(_, _, text)| text.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let summary = summarize_text(&full_text, max_len).await?;
+
+    let _ = app.emit("subtitle_progress", "Summary complete");
+    Ok(TranscriptSummary { summary, chapters })
+}
+
+/// Sends the transcript to the `summarize.py` sidecar and returns its summary.
+async fn summarize_text(text: &str, max_len: usize) -> Result<String, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = Command::new("python3")
+        .args(["summarize.py", &max_len.to_string()])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run summarizer: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send text to summarizer: {e}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Summarizer process error: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Parses ASS/SRT content into `(start_ms, end_ms, text)` tuples in order.
+fn parse_timed_segments(content: &str) -> Vec<(u64, u64, String)> {
+    let mut segments = Vec::new();
+
+    if content.lines().any(|l| l.starts_with("Dialogue:")) {
+        for line in content.lines() {
+            if let Some(rest) = line.strip_prefix("Dialogue:") {
+                let fields: Vec<&str> = rest.splitn(10, ',').collect();
+                if fields.len() >= 10 {
+                    segments.push((
+                        parse_ass_time(fields[1].trim()),
+                        parse_ass_time(fields[2].trim()),
+                        fields[9].to_string(),
+                    ));
+                }
+            }
+        }
+        return segments;
+    }
+
+    // SRT: a `-->` timing line followed by one or more text lines.
+    let mut lines = content.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some((start, end)) = line.split_once("-->") {
+            let start_ms = parse_srt_time(start.trim());
+            let end_ms = parse_srt_time(end.trim());
+            let mut text = String::new();
+            while let Some(next) = lines.peek() {
+                if next.trim().is_empty() {
+                    break;
+                }
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(next.trim());
+                lines.next();
+            }
+            segments.push((start_ms, end_ms, text));
+        }
+    }
+    segments
+}
+
+/// How translated captions are written back out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationMode {
+    /// Replace the original text with the translation.
+    Replace,
+    /// Stack the original on top of the translation (two lines per cue).
+    Bilingual,
+}
+
+/// Translates the text of every cue in a subtitle file into `target_lang`,
+/// preserving timing and segment count so the result can be fed straight into
+/// [`overlay_subtitles`]. Returns the path of the translated file.
+pub async fn translate_subtitles(
+    app: &tauri::AppHandle,
+    subtitle_path: &str,
+    target_lang: &str,
+    mode: TranslationMode,
+) -> Result<String, String> {
+    let _ = app.emit("subtitle_progress", format!("Translating captions to {target_lang}..."));
+
+    let content = std::fs::read_to_string(subtitle_path)
+        .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
+    let is_ass = content.lines().any(|l| l.starts_with("Dialogue:"));
+
+    // Collect the cue texts in document order.
+    let texts: Vec<String> = content
+        .lines()
+        .filter_map(|line| cue_text(line, is_ass).map(str::to_string))
+        .collect();
+    if texts.is_empty() {
+        return Err("No subtitle cues found to translate".to_string());
+    }
+
+    let translated = translate_lines(&texts, target_lang).await?;
+    if translated.len() != texts.len() {
+        return Err("Translation backend changed the segment count".to_string());
+    }
+
+    // `\N` is only a hard line break for ASS consumers; SRT just wants a
+    // literal newline, which `join("\n")` below happily renders as a second
+    // subtitle line.
+    let line_break = if is_ass { "\\N" } else { "\n" };
+
+    // Re-emit with the translated text, keeping every timestamp intact.
+    let mut next = translated.into_iter();
+    let rewritten = content
+        .lines()
+        .map(|line| {
+            let Some(original) = cue_text(line, is_ass) else {
+                return line.to_string();
+            };
+            let translation = next.next().unwrap_or_default();
+            let replacement = match mode {
+                TranslationMode::Replace => translation,
+                TranslationMode::Bilingual => format!("{original}{line_break}{translation}"),
+            };
+            line.replacen(original, &replacement, 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let output_path = translated_path(subtitle_path, target_lang);
+    std::fs::write(&output_path, rewritten)
+        .map_err(|e| format!("Failed to write translated subtitles: {e}"))?;
+
+    let _ = app.emit("subtitle_progress", "Translation complete");
+    Ok(output_path)
+}
+
+/// Translates an ASS file's cue text for [`crate::commands::transcribe::export_with_auto_subs`],
+/// reporting over the `export_log` channel that export-pipeline commands
+/// already use (rather than `subtitle_progress`, which is the standalone
+/// [`translate_subtitles`] command's channel). Preserves every field around
+/// the cue text (timing, style name, margins), so the result still feeds
+/// [`crate::video::emoji_overlay::convert_with_emoji_overlays`]. Override
+/// tags inside the cue text (e.g. `{\k..}` karaoke timing) are sent to the
+/// translator with the text and replaced by its output; they are not kept
+/// separately.
+pub async fn translate_ass_for_export(
+    app: &tauri::AppHandle,
+    subtitle_path: &str,
+    target_lang: &str,
+) -> Result<String, String> {
+    let content = std::fs::read_to_string(subtitle_path)
+        .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
+    let is_ass = content.lines().any(|l| l.starts_with("Dialogue:"));
+
+    let texts: Vec<String> = content
+        .lines()
+        .filter_map(|line| cue_text(line, is_ass).map(str::to_string))
+        .collect();
+    if texts.is_empty() {
+        return Err("No subtitle cues found to translate".to_string());
+    }
+    let total = texts.len();
+
+    let _ = app.emit("export_log", format!("🌐 Translating {total} caption(s) to {target_lang}..."));
+    let translated = translate_lines(&texts, target_lang).await?;
+    if translated.len() != total {
+        return Err("Translation backend changed the segment count".to_string());
+    }
+
+    for (i, line) in translated.iter().enumerate() {
+        let _ = app.emit("export_log", format!("  ({}/{total}) {line}", i + 1));
+    }
+
+    let mut next = translated.into_iter();
+    let rewritten = content
+        .lines()
+        .map(|line| {
+            let Some(original) = cue_text(line, is_ass) else {
+                return line.to_string();
+            };
+            let translation = next.next().unwrap_or_default();
+            line.replacen(original, &translation, 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let output_path = translated_path(subtitle_path, target_lang);
+    std::fs::write(&output_path, rewritten)
+        .map_err(|e| format!("Failed to write translated subtitles: {e}"))?;
+
+    let _ = app.emit("export_log", "✓ Caption translation complete");
+    Ok(output_path)
+}
+
+/// Returns the displayed text of a cue line (ASS `Dialogue:` or SRT body),
+/// or `None` for structural lines (headers, timing, indices). In ASS content
+/// (`is_ass`) only `Dialogue:` lines carry text.
+fn cue_text(line: &str, is_ass: bool) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix("Dialogue:") {
+        // Text is the 10th comma-separated field.
+        rest.splitn(10, ',').nth(9)
+    } else if is_ass
+        || line.contains("-->")
+        || line.trim().is_empty()
+        || line.trim().chars().all(|c| c.is_ascii_digit())
+    {
+        // Timing rows, indices and blank lines are not translatable.
+        None
+    } else {
+        Some(line)
+    }
+}
+
+/// Sends the cue texts to the `translate.py` sidecar (one line in, one line
+/// out) and returns the translations in order.
+async fn translate_lines(texts: &[String], target_lang: &str) -> Result<Vec<String>, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = Command::new("python3")
+        .args(["translate.py", target_lang])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run translator: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(texts.join("\n").as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send text to translator: {e}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Translator process error: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Derives the translated file path, e.g. `clip.ass` -> `clip.fr.ass`.
+fn translated_path(subtitle_path: &str, target_lang: &str) -> String {
+    let path = std::path::Path::new(subtitle_path);
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("srt");
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("subtitles");
+    path.with_file_name(format!("{stem}.{target_lang}.{ext}"))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Frame length (milliseconds) used when building voice-activity timelines.
+const VAD_FRAME_MS: u64 = 20;
+
+/// Bounded offset search window (milliseconds) for resynchronization (±30s).
+const RESYNC_WINDOW_MS: i64 = 30_000;
+
+/// Offset search step (milliseconds).
+const RESYNC_STEP_MS: i64 = 100;
+
+/// Realigns an existing (possibly drifted) SRT/ASS file to the video's audio.
+///
+/// A voice-activity timeline is derived from the 16kHz mono WAV (frame energy
+/// over ~20ms frames) and a second timeline from the subtitle display
+/// intervals. The global offset that maximizes overlap between the two is
+/// found by sliding one over the other across a bounded window; a small
+/// per-run scale factor corrects frame-rate mismatches. All timestamps are
+/// then rewritten and saved to `output_path`.
+pub async fn resync_subtitles(
+    app: &tauri::AppHandle,
+    audio_path: &str,
+    subtitle_path: &str,
+    output_path: &str,
+    tools: &ToolConfig,
+) -> Result<(), String> {
+    let _ = app.emit("subtitle_progress", "Analyzing audio for resync...");
+
+    // Reuse the ffmpeg extraction path for non-WAV inputs.
+    let wav_path = if audio_path.to_lowercase().ends_with(".wav") {
+        audio_path.to_string()
+    } else {
+        let has_video = probe_media(audio_path, tools).await.map(|m| m.has_video).unwrap_or(true);
+        extract_audio_for_transcription(app, audio_path, has_video, tools).await?
+    };
+
+    let speech = wav_speech_timeline(&wav_path)?;
+
+    let content = std::fs::read_to_string(subtitle_path)
+        .map_err(|e| format!("Failed to read subtitle file: {e}"))?;
+    let intervals = subtitle_intervals(&content);
+    if intervals.is_empty() {
+        return Err("No subtitle cues found to resync".to_string());
+    }
+
+    let (offset_ms, scale) = best_alignment(&speech, &intervals);
+    let _ = app.emit(
+        "subtitle_progress",
+        format!("Resync: offset {offset_ms}ms, scale {scale:.3}"),
+    );
+
+    let rewritten = rewrite_timestamps(&content, offset_ms, scale);
+    std::fs::write(output_path, rewritten)
+        .map_err(|e| format!("Failed to write resynced subtitles: {e}"))?;
+
+    // Clean up a WAV we extracted ourselves.
+    if wav_path != audio_path {
+        let _ = tokio::fs::remove_file(&wav_path).await;
+    }
+
+    let _ = app.emit("subtitle_progress", "Resync complete");
+    Ok(())
+}
+
+/// Reads a 16-bit PCM WAV and returns a per-frame speech/no-speech timeline
+/// using a frame-energy threshold.
+fn wav_speech_timeline(wav_path: &str) -> Result<Vec<bool>, String> {
+    let bytes = std::fs::read(wav_path).map_err(|e| format!("Failed to read WAV: {e}"))?;
+
+    // Locate the `data` subchunk and read the i16 samples after it.
+    let data_start = bytes
+        .windows(4)
+        .position(|w| w == b"data")
+        .map(|p| p + 8)
+        .ok_or_else(|| "WAV missing data chunk".to_string())?;
+
+    let samples: Vec<i16> = bytes[data_start..]
+        .chunks_exact(2)
+        .map(|c| i16::from_le_bytes([c[0], c[1]]))
+        .collect();
+
+    // 16kHz mono: 20ms == 320 samples per frame.
+    let frame_len = (16_000 * VAD_FRAME_MS / 1000) as usize;
+    if frame_len == 0 || samples.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let energies: Vec<f64> = samples
+        .chunks(frame_len)
+        .map(|frame| {
+            let sum: f64 = frame.iter().map(|&s| f64::from(s).powi(2)).sum();
+            (sum / frame.len() as f64).sqrt()
+        })
+        .collect();
+
+    // Threshold at a fraction of the mean RMS energy.
+    let mean = energies.iter().sum::<f64>() / energies.len().max(1) as f64;
+    let threshold = mean * 0.5;
+    Ok(energies.iter().map(|&e| e > threshold).collect())
+}
+
+/// Builds a per-frame speech timeline from subtitle display intervals, spanning
+/// the same number of frames as `reference_len`.
+fn intervals_timeline(intervals: &[(u64, u64)], reference_len: usize, shift_ms: i64, scale: f64) -> Vec<bool> {
+    let mut timeline = vec![false; reference_len];
+    for &(start, end) in intervals {
+        let s = ((start as f64 * scale) as i64 + shift_ms).max(0) as u64;
+        let e = ((end as f64 * scale) as i64 + shift_ms).max(0) as u64;
+        let s_frame = (s / VAD_FRAME_MS) as usize;
+        let e_frame = (e / VAD_FRAME_MS) as usize;
+        for frame in timeline.iter_mut().take(e_frame.min(reference_len)).skip(s_frame) {
+            *frame = true;
+        }
+    }
+    timeline
+}
+
+/// Slides the subtitle timeline over the speech timeline and returns the
+/// `(offset_ms, scale)` with the highest overlap.
+fn best_alignment(speech: &[bool], intervals: &[(u64, u64)]) -> (i64, f64) {
+    let mut best = (0_i64, 1.0_f64, 0_usize);
+
+    for scale_permille in [960, 980, 1000, 1020, 1040] {
+        let scale = f64::from(scale_permille) / 1000.0;
+        let mut shift = -RESYNC_WINDOW_MS;
+        while shift <= RESYNC_WINDOW_MS {
+            let candidate = intervals_timeline(intervals, speech.len(), shift, scale);
+            let overlap = speech
+                .iter()
+                .zip(&candidate)
+                .filter(|(a, b)| **a && **b)
+                .count();
+            if overlap > best.2 {
+                best = (shift, scale, overlap);
+            }
+            shift += RESYNC_STEP_MS;
+        }
+    }
+
+    (best.0, best.1)
+}
+
+/// Extracts `(start_ms, end_ms)` display intervals from ASS or SRT content.
+fn subtitle_intervals(content: &str) -> Vec<(u64, u64)> {
+    let mut intervals = Vec::new();
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("Dialogue:") {
+            let fields: Vec<&str> = rest.splitn(10, ',').collect();
+            if fields.len() >= 3 {
+                intervals.push((parse_ass_time(fields[1].trim()), parse_ass_time(fields[2].trim())));
+            }
+        } else if let Some((start, end)) = line.split_once("-->") {
+            intervals.push((parse_srt_time(start.trim()), parse_srt_time(end.trim())));
+        }
+    }
+    intervals
+}
+
+/// Parses an SRT timestamp (`HH:MM:SS,mmm`) into milliseconds.
+fn parse_srt_time(ts: &str) -> u64 {
+    let (hms, millis) = ts.split_once(',').unwrap_or((ts, "0"));
+    let parts: Vec<&str> = hms.split(':').collect();
+    if parts.len() != 3 {
+        return 0;
+    }
+    let hours: u64 = parts[0].parse().unwrap_or(0);
+    let minutes: u64 = parts[1].parse().unwrap_or(0);
+    let seconds: u64 = parts[2].parse().unwrap_or(0);
+    (hours * 3600 + minutes * 60 + seconds) * 1000 + millis.parse::<u64>().unwrap_or(0)
+}
+
+/// Applies `t -> t * scale + offset_ms` to every timestamp in ASS/SRT content.
+fn rewrite_timestamps(content: &str, offset_ms: i64, scale: f64) -> String {
+    let shift = |ms: u64| -> u64 { ((ms as f64 * scale) as i64 + offset_ms).max(0) as u64 };
+
+    content
+        .lines()
+        .map(|line| {
+            if let Some(rest) = line.strip_prefix("Dialogue:") {
+                let fields: Vec<&str> = rest.splitn(10, ',').collect();
+                if fields.len() < 10 {
+                    return line.to_string();
+                }
+                let start = format_ass_time(shift(parse_ass_time(fields[1].trim())));
+                let end = format_ass_time(shift(parse_ass_time(fields[2].trim())));
+                format!(
+                    "Dialogue:{},{start},{end},{}",
+                    fields[0],
+                    fields[3..].join(",")
+                )
+            } else if let Some((start, end)) = line.split_once("-->") {
+                format!(
+                    "{} --> {}",
+                    format_srt_time(shift(parse_srt_time(start.trim()))),
+                    format_srt_time(shift(parse_srt_time(end.trim())))
+                )
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats milliseconds as an ASS timestamp (`H:MM:SS.cs`).
+fn format_ass_time(ms: u64) -> String {
+    let cs = (ms % 1000) / 10;
+    let total_secs = ms / 1000;
+    format!("{}:{:02}:{:02}.{:02}", total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60, cs)
+}
+
+/// Formats milliseconds as an SRT timestamp (`HH:MM:SS,mmm`).
+fn format_srt_time(ms: u64) -> String {
+    let millis = ms % 1000;
+    let total_secs = ms / 1000;
+    format!("{:02}:{:02}:{:02},{:03}", total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60, millis)
+}
+
 /// Overlay subtitles onto video using `FFmpeg`
 ///
 /// # Arguments
@@ -202,6 +1230,9 @@ fn parse_subtitle_file(subtitle_path: &str) -> Result<SubtitleResult, String> {
 /// * `video_path` - Path to input video
 /// * `subtitle_path` - Path to subtitle file (.srt or .ass)
 /// * `output_path` - Path for output video
+/// * `target_vmaf` - Optional target VMAF score for the emoji-overlay path
+///   (ignored when the standard, non-emoji rendering path is used)
+/// * `tools` - Configured external-tool locations/extra args
 ///
 /// # Returns
 /// * `Ok(())` - Success
@@ -211,6 +1242,8 @@ pub async fn overlay_subtitles(
     video_path: &str,
     subtitle_path: &str,
     output_path: &str,
+    target_vmaf: Option<f64>,
+    tools: &ToolConfig,
 ) -> Result<(), String> {
     log::info!("Overlaying subtitles: {video_path} + {subtitle_path}");
     
@@ -236,6 +1269,8 @@ pub async fn overlay_subtitles(
             video_path,
             output_path,
             subtitle_path,
+            target_vmaf,
+            tools,
         ).await;
     }
     
@@ -1,4 +1,6 @@
 // Intelligent clip detection and extraction
+use crate::settings::ToolConfig;
+use crate::video::vmaf;
 use serde::{Deserialize, Serialize};
 use std::process::Stdio;
 use tauri::Emitter;
@@ -22,18 +24,19 @@ pub async fn detect_clips_by_scenes(
     min_clip_duration: f64,  // Minimum clip length in seconds (e.g., 15)
     max_clip_duration: f64,  // Maximum clip length in seconds (e.g., 60)
     scene_threshold: f64,    // Scene change sensitivity (0.1-0.9, default 0.3)
+    tools: &ToolConfig,
 ) -> Result<Vec<Clip>, String> {
     let _ = app.emit("export_log", "🔍 Starting intelligent clip detection...");
     let _ = app.emit("export_log", format!("📋 Settings: {min_clip_duration:.0}s-{max_clip_duration:.0}s clips, scene threshold: {scene_threshold:.2}"));
 
     let _ = app.emit("export_log", "🎬 Analyzing video for scene changes...");
-    
+
     // Get video duration first
-    let video_duration = get_video_duration(video_path).await?;
+    let video_duration = get_video_duration(video_path, tools).await?;
     let _ = app.emit("export_log", format!("📊 Video duration: {video_duration:.1}s"));
-    
+
     // Use FFmpeg's scene detection filter - faster with lower resolution
-    let mut child = Command::new("ffmpeg")
+    let mut child = Command::new(tools.ffmpeg())
         .args([
             "-i",
             video_path,
@@ -95,86 +98,295 @@ pub async fn detect_clips_by_scenes(
     );
 
     // If no scene changes found, fall back to time-based detection
-    if scene_times.is_empty() {
+    let mut clips = if scene_times.is_empty() {
         let _ = app.emit("export_log", "⚠️ No scene changes found, using time-based detection");
-        return Ok(detect_clips_by_time(video_duration, f64::midpoint(min_clip_duration, max_clip_duration), 2.0));
+        detect_clips_by_time(video_duration, f64::midpoint(min_clip_duration, max_clip_duration), 2.0)
+    } else {
+        let _ = app.emit("export_log", "🎯 Creating clips from scene data...");
+
+        // Convert scene changes into clips
+        let mut clips = Vec::new();
+        let mut current_start = 0.0;
+
+        for &scene_time in &scene_times {
+            push_duration_bounded_clip(&mut clips, current_start, scene_time, min_clip_duration, max_clip_duration);
+            current_start = scene_time;
+        }
+
+        // Handle the last segment from the last scene change to end of video
+        push_duration_bounded_clip(&mut clips, current_start, video_duration, min_clip_duration, max_clip_duration);
+
+        clips
+    };
+
+    score_and_rank_clips(app, video_path, &mut clips, &scene_times, tools).await?;
+
+    let _ = app.emit(
+        "export_log",
+        format!("✅ Generated {} clips (total duration: {:.1}s)", clips.len(), clips.iter().map(|c| c.duration).sum::<f64>()),
+    );
+
+    Ok(clips)
+}
+
+/// Small gap below which two loud regions are merged into a single clip
+/// candidate instead of being split by a brief pause (breath, throat-clear).
+const SILENCE_MERGE_PAD_SECONDS: f64 = 0.75;
+
+/// Pushes `[start, end)` onto `clips` as one or more `Clip`s honoring
+/// `min_clip_duration`/`max_clip_duration`: dropped if too short, split
+/// evenly into same-length pieces if too long, otherwise kept as-is. Shared
+/// by the scene- and silence-based detectors so both obey the same
+/// min/max-duration contract.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn push_duration_bounded_clip(
+    clips: &mut Vec<Clip>,
+    start: f64,
+    end: f64,
+    min_clip_duration: f64,
+    max_clip_duration: f64,
+) {
+    let duration = end - start;
+    if duration < min_clip_duration {
+        return;
     }
 
-    let _ = app.emit("export_log", "🎯 Creating clips from scene data...");
-    
-    // Convert scene changes into clips
-    let mut clips = Vec::new();
-    let mut current_start = 0.0;
+    if duration <= max_clip_duration {
+        clips.push(Clip { start_time: start, end_time: end, duration, score: 0.5 });
+        return;
+    }
+
+    // Split long segments into evenly-sized clips.
+    let num_clips = (duration / max_clip_duration).ceil() as usize;
+    let clip_duration = duration / num_clips as f64;
+    for i in 0..num_clips {
+        let clip_start = (i as f64).mul_add(clip_duration, start);
+        let clip_end = if i == num_clips - 1 { end } else { clip_start + clip_duration };
+        clips.push(Clip {
+            start_time: clip_start,
+            end_time: clip_end,
+            duration: clip_end - clip_start,
+            score: 0.5,
+        });
+    }
+}
 
-    for &scene_time in &scene_times {
-        let duration = scene_time - current_start;
+/// Relative weight of mean loudness vs. scene-cut density in the combined
+/// interest score.
+const LOUDNESS_WEIGHT: f64 = 0.6;
+const CUT_DENSITY_WEIGHT: f64 = 0.4;
 
-        // If the segment is within our duration range, create a clip
-        if duration >= min_clip_duration && duration <= max_clip_duration {
-            clips.push(Clip {
-                start_time: current_start,
-                end_time: scene_time,
-                duration,
-                score: 0.5, // Placeholder score
-            });
-        } else if duration > max_clip_duration {
-            // Split long segments into multiple clips
-            let num_clips = (duration / max_clip_duration).ceil() as usize;
-            let clip_duration = duration / num_clips as f64;
-
-            for i in 0..num_clips {
-                let start = (i as f64).mul_add(clip_duration, current_start);
-                let end = start + clip_duration;
-                clips.push(Clip {
-                    start_time: start,
-                    end_time: end,
-                    duration: clip_duration,
-                    score: 0.5,
-                });
+/// dB range `volumedetect`'s mean_volume is normalized against: at or below
+/// the floor scores 0, at or above the ceiling scores 1.
+const LOUDNESS_FLOOR_DB: f64 = -40.0;
+const LOUDNESS_CEILING_DB: f64 = -5.0;
+
+/// Cut density (cuts/second) at or above which the density score saturates
+/// at 1.
+const CUT_DENSITY_CEILING_PER_SEC: f64 = 0.5;
+
+/// Scores each clip 0-1 from mean loudness (`volumedetect`) and scene-cut
+/// density within its time range, combines them into `clip.score`, and
+/// sorts `clips` descending by that score so the strongest candidates come
+/// first. `scene_times` is the full list of scene-change pts_times already
+/// collected for the video; pass an empty slice when none are available
+/// (e.g. silence-based detection), in which case only loudness contributes.
+#[allow(clippy::cast_precision_loss)]
+pub async fn score_and_rank_clips(
+    app: &tauri::AppHandle,
+    video_path: &str,
+    clips: &mut Vec<Clip>,
+    scene_times: &[f64],
+    tools: &ToolConfig,
+) -> Result<(), String> {
+    let _ = app.emit("export_log", "📊 Scoring clips by loudness and cut density...");
+
+    for clip in clips.iter_mut() {
+        let loudness_db = measure_mean_loudness(video_path, clip.start_time, clip.duration, tools)
+            .await
+            .unwrap_or(LOUDNESS_FLOOR_DB);
+        let loudness_score =
+            ((loudness_db - LOUDNESS_FLOOR_DB) / (LOUDNESS_CEILING_DB - LOUDNESS_FLOOR_DB)).clamp(0.0, 1.0);
+
+        let cut_count = scene_times.iter().filter(|&&t| t >= clip.start_time && t < clip.end_time).count();
+        let cut_density = cut_count as f64 / clip.duration.max(0.001);
+        let density_score = (cut_density / CUT_DENSITY_CEILING_PER_SEC).clamp(0.0, 1.0);
+
+        clip.score =
+            (LOUDNESS_WEIGHT.mul_add(loudness_score, CUT_DENSITY_WEIGHT * density_score)).clamp(0.0, 1.0);
+
+        let _ = app.emit(
+            "export_log",
+            format!(
+                "  {:.1}s-{:.1}s: score {:.2} (loudness {loudness_db:.1}dB → {loudness_score:.2}, {cut_count} cuts → {density_score:.2})",
+                clip.start_time, clip.end_time, clip.score
+            ),
+        );
+    }
+
+    clips.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(())
+}
+
+/// Runs FFmpeg's `volumedetect` filter over `duration` seconds of
+/// `video_path` starting at `start` and parses the reported mean volume in
+/// dB (silence floors out at very negative values, e.g. -91dB).
+async fn measure_mean_loudness(video_path: &str, start: f64, duration: f64, tools: &ToolConfig) -> Result<f64, String> {
+    let output = Command::new(tools.ffmpeg())
+        .args([
+            "-i",
+            video_path,
+            "-ss",
+            &start.to_string(),
+            "-t",
+            &duration.to_string(),
+            "-af",
+            "volumedetect",
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run volumedetect: {e}"))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr
+        .lines()
+        .find_map(|line| {
+            let idx = line.find("mean_volume:")?;
+            let rest = line[idx + "mean_volume:".len()..].trim();
+            rest.split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .ok_or_else(|| "volumedetect did not report mean_volume".to_string())
+}
+
+/// Detects clips using silence gaps (auto-editor style) rather than visual
+/// scene cuts — far better suited to talking-head/podcast content where
+/// [`detect_clips_by_scenes`] often finds no cuts at all.
+///
+/// Runs FFmpeg's `silencedetect` filter, scrapes `silence_start:`/
+/// `silence_end:` timestamps from stderr (mirroring how
+/// [`detect_clips_by_scenes`] scrapes `pts_time:`), inverts the silences into
+/// "loud" intervals spanning `[0, video_duration]`, merges loud intervals
+/// separated by a brief pause, then applies the same min/max-duration
+/// splitting as scene detection. Falls back to time-based detection if no
+/// silences are found.
+#[allow(clippy::too_many_lines, clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+pub async fn detect_clips_by_silence(
+    app: &tauri::AppHandle,
+    video_path: &str,
+    min_clip_duration: f64, // Minimum clip length in seconds (e.g., 15)
+    max_clip_duration: f64, // Maximum clip length in seconds (e.g., 60)
+    noise_threshold_db: f64, // Silence noise floor in dB (negative, e.g. -30)
+    min_silence_duration: f64, // Minimum gap length to count as silence (e.g. 0.5)
+    tools: &ToolConfig,
+) -> Result<Vec<Clip>, String> {
+    let _ = app.emit("export_log", "🔍 Starting silence-based clip detection...");
+    let _ = app.emit(
+        "export_log",
+        format!(
+            "📋 Settings: {min_clip_duration:.0}s-{max_clip_duration:.0}s clips, noise floor: {noise_threshold_db:.0}dB, min silence: {min_silence_duration:.2}s"
+        ),
+    );
+
+    // Get video duration first
+    let video_duration = get_video_duration(video_path, tools).await?;
+    let _ = app.emit("export_log", format!("📊 Video duration: {video_duration:.1}s"));
+
+    let _ = app.emit("export_log", "🔊 Analyzing audio for silence...");
+
+    let mut child = Command::new(tools.ffmpeg())
+        .args([
+            "-i",
+            video_path,
+            "-af",
+            &format!("silencedetect=noise={noise_threshold_db}dB:d={min_silence_duration}"),
+            "-f",
+            "null",
+            "-",
+        ])
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run silence detection: {e}"))?;
+
+    let mut silences: Vec<(f64, f64)> = Vec::new();
+    let mut pending_start: Option<f64> = None;
+
+    // Parse FFmpeg output for silence_start/silence_end timestamps
+    if let Some(stderr) = child.stderr.take() {
+        let mut reader = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = reader.next_line().await {
+            if let Some(idx) = line.find("silence_start:") {
+                let time_str = line[idx + "silence_start:".len()..].trim();
+                if let Ok(time) = time_str.split_whitespace().next().unwrap_or("").parse::<f64>() {
+                    pending_start = Some(time);
+                }
+            } else if let Some(idx) = line.find("silence_end:") {
+                let time_str = line[idx + "silence_end:".len()..].trim();
+                if let Ok(time) = time_str.split_whitespace().next().unwrap_or("").parse::<f64>() {
+                    if let Some(start) = pending_start.take() {
+                        silences.push((start, time));
+                    }
+                }
             }
-        } else {
-            // Duration is too short, do nothing and wait for the next segment.
         }
-        current_start = scene_time;
     }
 
-    // Handle the last segment from the last scene change to end of video
-    let last_duration = video_duration - current_start;
-    if last_duration >= min_clip_duration {
-        if last_duration <= max_clip_duration {
-            clips.push(Clip {
-                start_time: current_start,
-                end_time: video_duration,
-                duration: last_duration,
-                score: 0.5,
-            });
-        } else {
-            // Split the last long segment
-            let num_clips = (last_duration / max_clip_duration).ceil() as usize;
-            let clip_duration = last_duration / num_clips as f64;
-            
-            for i in 0..num_clips {
-                let start = (i as f64).mul_add(clip_duration, current_start);
-                let end = if i == num_clips - 1 {
-                    video_duration
-                } else {
-                    start + clip_duration
-                };
-                clips.push(Clip {
-                    start_time: start,
-                    end_time: end,
-                    duration: end - start,
-                    score: 0.5,
-                });
+    let _ = child.wait().await;
+
+    silences.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let _ = app.emit("export_log", format!("✓ Silence detection complete: {} silences found", silences.len()));
+
+    // If no silences found, fall back to time-based detection
+    let mut clips = if silences.is_empty() {
+        let _ = app.emit("export_log", "⚠️ No silences found, using time-based detection");
+        detect_clips_by_time(video_duration, f64::midpoint(min_clip_duration, max_clip_duration), 2.0)
+    } else {
+        let _ = app.emit("export_log", "🎯 Creating clips from spoken segments...");
+
+        // Invert silence intervals into the "loud" intervals between them.
+        let mut loud_intervals: Vec<(f64, f64)> = Vec::new();
+        let mut cursor = 0.0;
+        for &(silence_start, silence_end) in &silences {
+            if silence_start > cursor {
+                loud_intervals.push((cursor, silence_start));
             }
+            cursor = silence_end.max(cursor);
+        }
+        if cursor < video_duration {
+            loud_intervals.push((cursor, video_duration));
         }
-    }
+
+        // Merge loud regions separated by only a brief pause.
+        let mut merged_intervals: Vec<(f64, f64)> = Vec::new();
+        for (start, end) in loud_intervals {
+            if let Some(last) = merged_intervals.last_mut() {
+                if start - last.1 < SILENCE_MERGE_PAD_SECONDS {
+                    last.1 = end;
+                    continue;
+                }
+            }
+            merged_intervals.push((start, end));
+        }
+
+        let mut clips = Vec::new();
+        for (start, end) in merged_intervals {
+            push_duration_bounded_clip(&mut clips, start, end, min_clip_duration, max_clip_duration);
+        }
+        clips
+    };
+
+    // No visual cut data in this path; loudness alone drives the score.
+    score_and_rank_clips(app, video_path, &mut clips, &[], tools).await?;
 
     let _ = app.emit(
         "export_log",
         format!("✅ Generated {} clips (total duration: {:.1}s)", clips.len(), clips.iter().map(|c| c.duration).sum::<f64>()),
     );
-    
+
     Ok(clips)
 }
 
@@ -209,33 +421,67 @@ pub fn detect_clips_by_time(
     clips
 }
 
-/// Extracts a specific clip from a video
-/// Outputs a new video file with the specified time range
+/// Extracts a specific clip from a video, outputting a new video file with
+/// the specified time range.
+///
+/// By default this is a fast stream copy (no re-encode). When `target_vmaf`
+/// is set, the clip is re-encoded with libx264 at a CRF chosen by probing a
+/// short sample, aiming for within ±1 VMAF point of the requested score
+/// (see [`crate::video::vmaf`]).
 pub async fn extract_clip(
     app: &tauri::AppHandle,
     input_path: &str,
     output_path: &str,
     start_time: f64,
     duration: f64,
+    target_vmaf: Option<f64>,
+    tools: &ToolConfig,
 ) -> Result<(), String> {
     let _ = app.emit(
         "export_log",
         format!("✂️ Extracting clip: {:.1}s → {:.1}s ({:.1}s duration)", start_time, start_time + duration, duration),
     );
 
-    let mut child = Command::new("ffmpeg")
-        .args([
-            "-y",
-            "-ss",
-            &start_time.to_string(),
-            "-i",
-            input_path,
-            "-t",
-            &duration.to_string(),
-            "-c",
-            "copy", // Fast copy without re-encoding
-            output_path,
-        ])
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        start_time.to_string(),
+        "-i".to_string(),
+        input_path.to_string(),
+        "-t".to_string(),
+        duration.to_string(),
+    ];
+
+    match target_vmaf {
+        Some(target) => {
+            let crf = vmaf::find_crf_for_target_vmaf(
+                app,
+                input_path,
+                "null",
+                start_time,
+                duration,
+                target,
+                tools,
+            )
+            .await?;
+            args.extend([
+                "-c:v".to_string(),
+                "libx264".to_string(),
+                "-preset".to_string(),
+                "fast".to_string(),
+                "-crf".to_string(),
+                crf.to_string(),
+                "-c:a".to_string(),
+                "copy".to_string(),
+            ]);
+        }
+        None => args.extend(["-c".to_string(), "copy".to_string()]), // Fast copy without re-encoding
+    }
+    args.extend(tools.extra_args.iter().cloned());
+    args.push(output_path.to_string());
+
+    let mut child = Command::new(tools.ffmpeg())
+        .args(&args)
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| format!("Failed to extract clip: {e}"))?;
@@ -259,8 +505,8 @@ pub async fn extract_clip(
 }
 
 /// Gets video duration in seconds
-pub async fn get_video_duration(video_path: &str) -> Result<f64, String> {
-    let output = Command::new("ffprobe")
+pub async fn get_video_duration(video_path: &str, tools: &ToolConfig) -> Result<f64, String> {
+    let output = Command::new(tools.ffprobe())
         .args([
             "-v",
             "error",
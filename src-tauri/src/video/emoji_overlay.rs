@@ -1,6 +1,8 @@
 // Emoji overlay system using image-based rendering
 // This bypasses libass limitations and renders emojis as PNG overlays
 
+use crate::settings::ToolConfig;
+use crate::video::{clipper, vmaf};
 use std::path::Path;
 use tauri::Emitter;
 use tokio::{
@@ -8,13 +10,23 @@ use tokio::{
     process::Command,
 };
 
+/// Base scale/pad chain shared by every export path, used as the probe
+/// filter for VMAF CRF selection — the emoji/subtitle overlays added on top
+/// don't meaningfully shift the CRF needed to hit a target VMAF.
+const BASE_SCALE_FILTER: &str = "scale=1080:1920:force_original_aspect_ratio=decrease,pad=1080:1920:(ow-iw)/2:(oh-ih)/2:color=black,setsar=1,format=yuv420p";
+
 /// Converts video with subtitles AND emoji overlays
-/// Uses image-based emoji rendering for guaranteed emoji support
+/// Uses image-based emoji rendering for guaranteed emoji support.
+///
+/// `target_vmaf`, when set, replaces the hard-coded CRF 20 with one chosen
+/// by probing a short sample (see [`crate::video::vmaf`]).
 pub async fn convert_with_emoji_overlays(
     app: &tauri::AppHandle,
     input: &str,
     output: &str,
     subtitle_file: &str,
+    target_vmaf: Option<f64>,
+    tools: &ToolConfig,
 ) -> Result<(), String> {
     let _ = app.emit(
         "export_log",
@@ -32,7 +44,7 @@ pub async fn convert_with_emoji_overlays(
             "📦 Generating emoji images (first time only)...",
         );
 
-        let output = Command::new("python3")
+        let output = Command::new(tools.python())
             .arg("emoji_to_image.py")
             .output()
             .await
@@ -50,7 +62,7 @@ pub async fn convert_with_emoji_overlays(
     // Step 2: Parse ASS file and generate overlay filter
     let _ = app.emit("export_log", "🔍 Analyzing emojis in subtitles...");
 
-    let filter_output = Command::new("python3")
+    let filter_output = Command::new(tools.python())
         .args(["scripts/generate_emoji_overlays.py", subtitle_file])
         .output()
         .await
@@ -69,6 +81,24 @@ pub async fn convert_with_emoji_overlays(
     // Step 4: Build FFmpeg command with both subtitles and emoji overlays
     let _ = app.emit("export_log", "🎬 Rendering video with emojis...");
 
+    let crf = match target_vmaf {
+        Some(target) => {
+            let duration = clipper::get_video_duration(input, tools).await?;
+            vmaf::find_crf_for_target_vmaf(
+                app,
+                input,
+                BASE_SCALE_FILTER,
+                0.0,
+                duration,
+                target,
+                tools,
+            )
+            .await?
+        }
+        None => 20,
+    };
+    let crf_str = crf.to_string();
+
     // Escape subtitle path
     let sub_escaped = subtitle_file
         .replace('\\', "\\\\")
@@ -87,28 +117,29 @@ pub async fn convert_with_emoji_overlays(
         )
     };
 
-    let args = vec![
-        "-y",
-        "-i",
-        input,
-        "-filter_complex",
-        &filter_complex,
-        "-map",
-        "[vN]", // Use final video output from filter chain
-        "-c:v",
-        "libx264",
-        "-preset",
-        "fast",
-        "-crf",
-        "20",
-        "-c:a",
-        "aac",
-        "-b:a",
-        "192k",
-        output,
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        input.to_string(),
+        "-filter_complex".to_string(),
+        filter_complex,
+        "-map".to_string(),
+        "[vN]".to_string(), // Use final video output from filter chain
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "fast".to_string(),
+        "-crf".to_string(),
+        crf_str,
+        "-c:a".to_string(),
+        "aac".to_string(),
+        "-b:a".to_string(),
+        "192k".to_string(),
     ];
+    args.extend(tools.extra_args.iter().cloned());
+    args.push(output.to_string());
 
-    let mut child = Command::new("ffmpeg")
+    let mut child = Command::new(tools.ffmpeg())
         .args(&args)
         .stderr(std::process::Stdio::piped())
         .spawn()
@@ -1,42 +1,71 @@
 // FFmpeg wrapper utilities for video processing with SaaS-quality error handling
+use crate::settings::ToolConfig;
+use crate::video::vmaf;
 use tauri::Emitter;
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::Command,
 };
 
+const TIKTOK_SCALE_FILTER: &str = "scale=1080:1920:force_original_aspect_ratio=decrease,pad=1080:1920:(ow-iw)/2:(oh-ih)/2:color=black,setsar=1,format=yuv420p";
+
 /// Converts a video to `TikTok` format (1080x1920, 9:16 aspect ratio)
-/// Emits progress logs to the frontend via the app handle
+/// Emits progress logs to the frontend via the app handle.
+///
+/// `target_vmaf`, when set, replaces the hard-coded CRF 20 with one chosen
+/// by probing a short sample, aiming for within ±1 VMAF point of the
+/// requested score (see [`crate::video::vmaf`]).
 pub async fn convert_to_tiktok(
     app: &tauri::AppHandle,
     input: &str,
     output: &str,
+    target_vmaf: Option<f64>,
+    tools: &ToolConfig,
 ) -> Result<(), String> {
-    let args = vec![
-        "-y",       // Overwrite output file
-        "-i",
-        input,
-        "-vf",
+    let crf = match target_vmaf {
+        Some(target) => {
+            let duration = crate::video::clipper::get_video_duration(input, tools).await?;
+            vmaf::find_crf_for_target_vmaf(
+                app,
+                input,
+                TIKTOK_SCALE_FILTER,
+                0.0,
+                duration,
+                target,
+                tools,
+            )
+            .await?
+        }
+        None => 20,
+    };
+    let crf_str = crf.to_string();
+
+    let mut args = vec![
+        "-y".to_string(),       // Overwrite output file
+        "-i".to_string(),
+        input.to_string(),
+        "-vf".to_string(),
         // Scale to fit within 1080x1920 maintaining aspect, then pad to exact size
-        "scale=1080:1920:force_original_aspect_ratio=decrease,pad=1080:1920:(ow-iw)/2:(oh-ih)/2:color=black,setsar=1,format=yuv420p",
-        "-c:v",
-        "libx264",  // H.264 codec
-        "-preset",
-        "fast",     // Changed from "medium" to "fast" for better performance
-        "-crf",
-        "20",       // Quality (lower = better, 18-23 is good)
-        "-c:a",
-        "aac",      // Audio codec
-        "-b:a",
-        "192k",     // Audio bitrate
-        "-progress",
-        "pipe:2",   // Output progress to stderr
-        output,
+        TIKTOK_SCALE_FILTER.to_string(),
+        "-c:v".to_string(),
+        "libx264".to_string(),  // H.264 codec
+        "-preset".to_string(),
+        "fast".to_string(),     // Changed from "medium" to "fast" for better performance
+        "-crf".to_string(),
+        crf_str,   // Quality (lower = better, 18-23 is good; auto-picked when target_vmaf is set)
+        "-c:a".to_string(),
+        "aac".to_string(),      // Audio codec
+        "-b:a".to_string(),
+        "192k".to_string(),     // Audio bitrate
+        "-progress".to_string(),
+        "pipe:2".to_string(),   // Output progress to stderr
     ];
+    args.extend(tools.extra_args.iter().cloned());
+    args.push(output.to_string());
 
     let _ = app.emit("export_log", "Starting FFmpeg conversion...");
 
-    let mut child = Command::new("ffmpeg")
+    let mut child = Command::new(tools.ffmpeg())
         .args(&args)
         .stderr(std::process::Stdio::piped())
         .spawn()
@@ -0,0 +1,150 @@
+// In-app YouTube search backed by the Invidious JSON API.
+//
+// Lets creators discover source material without leaving the app to copy a
+// URL: a selected result's `video_id` is turned into a watch URL that feeds
+// straight into the existing download/export pipeline.
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+/// Invidious instances tried in order; if one is down or rate-limits, the next
+/// is used automatically.
+const INVIDIOUS_INSTANCES: &[&str] = &[
+    "https://invidious.fdn.fr",
+    "https://inv.nadeko.net",
+    "https://yewtu.be",
+    "https://invidious.nerdvpn.de",
+];
+
+/// A single search hit returned to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub video_id: String,
+    pub title: String,
+    pub author: String,
+    /// Length in seconds (0 for live streams).
+    pub duration: u64,
+    pub thumbnail_url: String,
+}
+
+impl SearchResult {
+    /// Builds the canonical watch URL so the result can be handed to
+    /// [`crate::video::youtube::download_youtube_video`].
+    #[must_use]
+    pub fn watch_url(&self) -> String {
+        format!("https://www.youtube.com/watch?v={}", self.video_id)
+    }
+}
+
+/// Searches YouTube via Invidious, falling back across instances on failure.
+pub async fn search_youtube(
+    app: &tauri::AppHandle,
+    query: &str,
+) -> Result<Vec<SearchResult>, String> {
+    let _ = app.emit("export_log", format!("🔎 Searching YouTube for: {query}"));
+
+    let client = reqwest::Client::new();
+    let mut last_error = String::from("no Invidious instances configured");
+
+    for instance in INVIDIOUS_INSTANCES {
+        let url = format!("{instance}/api/v1/search?q={}&type=video", urlencode(query));
+
+        match fetch_results(&client, &url).await {
+            Ok(results) => {
+                let _ = app.emit(
+                    "export_log",
+                    format!("✓ {} results from {instance}", results.len()),
+                );
+                return Ok(results);
+            }
+            Err(e) => {
+                let _ = app.emit("export_log", format!("⚠️ {instance} failed: {e}, trying next..."));
+                last_error = e;
+            }
+        }
+    }
+
+    Err(format!("All Invidious instances failed. Last error: {last_error}"))
+}
+
+/// Fetches metadata for a single video id (used after a search pick).
+pub async fn get_video(
+    app: &tauri::AppHandle,
+    video_id: &str,
+) -> Result<SearchResult, String> {
+    let _ = app.emit("export_log", format!("🔎 Fetching video {video_id}..."));
+
+    let client = reqwest::Client::new();
+    let mut last_error = String::from("no Invidious instances configured");
+
+    for instance in INVIDIOUS_INSTANCES {
+        let url = format!("{instance}/api/v1/videos/{video_id}");
+
+        match client.get(&url).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                let json: serde_json::Value = resp
+                    .json()
+                    .await
+                    .map_err(|e| format!("Failed to parse video response: {e}"))?;
+                return Ok(parse_result(&json));
+            }
+            Ok(resp) => last_error = format!("HTTP {}", resp.status()),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+
+    Err(format!("All Invidious instances failed. Last error: {last_error}"))
+}
+
+/// Performs one search request and parses the result array.
+async fn fetch_results(client: &reqwest::Client, url: &str) -> Result<Vec<SearchResult>, String> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+
+    let items: Vec<serde_json::Value> = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse search response: {e}"))?;
+
+    Ok(items.iter().map(parse_result).collect())
+}
+
+/// Maps an Invidious video object into a [`SearchResult`].
+fn parse_result(item: &serde_json::Value) -> SearchResult {
+    // Invidious returns thumbnails as an array of `{quality, url}`; take the
+    // first entry as a reasonable default.
+    let thumbnail_url = item["videoThumbnails"]
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|t| t["url"].as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    SearchResult {
+        video_id: item["videoId"].as_str().unwrap_or_default().to_string(),
+        title: item["title"].as_str().unwrap_or("Unknown").to_string(),
+        author: item["author"].as_str().unwrap_or("Unknown").to_string(),
+        duration: item["lengthSeconds"].as_u64().unwrap_or(0),
+        thumbnail_url,
+    }
+}
+
+/// Minimal percent-encoding for the query string (spaces and reserved chars).
+fn urlencode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
@@ -0,0 +1,193 @@
+// VMAF target-quality mode: pick the encoder CRF automatically instead of
+// hard-coding one, mirroring Av1an's target-quality search.
+//
+// A handful of CRF probes (short sample encode + libvmaf score against the
+// source) binary-search toward the requested VMAF so callers can say "keep
+// this clip at VMAF 93" instead of guessing a CRF number.
+
+use crate::settings::ToolConfig;
+use std::path::Path;
+use tauri::Emitter;
+use tokio::process::Command;
+
+/// Lower bound of the CRF search range (highest quality we'll try).
+const MIN_CRF: i32 = 18;
+/// Upper bound of the CRF search range (lowest quality we'll try).
+const MAX_CRF: i32 = 34;
+/// Binary search converges well within this many probes over an 18-34 range.
+const MAX_PROBES: u32 = 4;
+/// Accept a probe once it lands within this many VMAF points of the target.
+const VMAF_TOLERANCE: f64 = 1.0;
+/// Probes encode at most this many seconds of the clip so a long clip
+/// doesn't pay full-length encode cost once per probe.
+const PROBE_SAMPLE_SECONDS: f64 = 6.0;
+
+/// Binary-searches CRF in `[18, 34]` for encoding `input` with `filter` to
+/// land within ±1 VMAF point of `target_vmaf`, probing a short sample
+/// starting at `start_time` (or the whole clip if it's shorter than the
+/// sample window) at each candidate, using `tools.ffmpeg()` so a configured
+/// binary path is honoured. The search stops at the first probe within
+/// tolerance, after `MAX_PROBES` probes, or when the range is exhausted, and
+/// returns the CRF of the last probe (which may be outside tolerance); the
+/// caller should reuse it for the real, full-quality encode.
+pub async fn find_crf_for_target_vmaf(
+    app: &tauri::AppHandle,
+    input: &str,
+    filter: &str,
+    start_time: f64,
+    clip_duration: f64,
+    target_vmaf: f64,
+    tools: &ToolConfig,
+) -> Result<u32, String> {
+    let sample_duration = clip_duration.min(PROBE_SAMPLE_SECONDS);
+
+    let mut low = MIN_CRF;
+    let mut high = MAX_CRF;
+    let mut best_crf = (low + high) / 2;
+
+    for probe in 1..=MAX_PROBES {
+        let crf = (low + high) / 2;
+        let measured = probe_vmaf(input, filter, start_time, sample_duration, crf, tools).await?;
+
+        let _ = app.emit(
+            "export_log",
+            format!(
+                "🎯 VMAF probe {probe}/{MAX_PROBES}: CRF {crf} → VMAF {measured:.1} (target {target_vmaf:.1})"
+            ),
+        );
+
+        best_crf = crf;
+        if (measured - target_vmaf).abs() <= VMAF_TOLERANCE {
+            break;
+        }
+
+        // Higher VMAF needs a lower CRF (less compression) and vice versa.
+        if measured < target_vmaf {
+            high = crf - 1;
+        } else {
+            low = crf + 1;
+        }
+
+        if low > high {
+            break;
+        }
+    }
+
+    let _ = app.emit(
+        "export_log",
+        format!("✓ Selected CRF {best_crf} for target VMAF {target_vmaf:.1}"),
+    );
+    Ok(best_crf as u32)
+}
+
+/// Encodes `sample_duration` seconds of `input` starting at `start_time` at
+/// `crf` (with `filter` applied) into a scratch file, scores it against the
+/// same region of the source (passed through the same `filter`) with
+/// FFmpeg's `libvmaf` filter, and returns the mean VMAF.
+async fn probe_vmaf(
+    input: &str,
+    filter: &str,
+    start_time: f64,
+    sample_duration: f64,
+    crf: i32,
+    tools: &ToolConfig,
+) -> Result<f64, String> {
+    let probe_dir = std::env::temp_dir();
+    let encoded_path = probe_dir.join(format!("vmaf_probe_crf{crf}.mp4"));
+    let log_path = probe_dir.join(format!("vmaf_probe_crf{crf}.json"));
+    let start_time_str = start_time.to_string();
+
+    let encode = Command::new(tools.ffmpeg())
+        .args([
+            "-y",
+            "-ss",
+            &start_time_str,
+            "-i",
+            input,
+            "-t",
+            &sample_duration.to_string(),
+            "-vf",
+            filter,
+            "-c:v",
+            "libx264",
+            "-preset",
+            "fast",
+            "-crf",
+            &crf.to_string(),
+            "-an",
+            path_str(&encoded_path)?,
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run VMAF probe encode: {e}"))?;
+
+    if !encode.status.success() {
+        return Err(format!(
+            "VMAF probe encode failed at CRF {crf}: {}",
+            String::from_utf8_lossy(&encode.stderr)
+        ));
+    }
+
+    // The encoded sample went through `filter` (e.g. a 1080x1920 scale), but
+    // the reference is still the untouched source at its original
+    // resolution — libvmaf aborts if ref/dist geometry doesn't match, so
+    // apply the same filter to the reference before scoring. The reference
+    // input is also seeked to `start_time` so it's scoring the same region
+    // of the source the sample was encoded from, not the start of the video.
+    let vmaf_filter = format!(
+        "[0:v]trim=duration={sample_duration}[dist];[1:v]{filter},trim=duration={sample_duration}[ref];[dist][ref]libvmaf=log_fmt=json:log_path={}",
+        log_path.display()
+    );
+
+    let score = Command::new(tools.ffmpeg())
+        .args([
+            "-y",
+            "-i",
+            path_str(&encoded_path)?,
+            "-ss",
+            &start_time_str,
+            "-i",
+            input,
+            "-filter_complex",
+            &vmaf_filter,
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run libvmaf: {e}"))?;
+
+    if !score.status.success() {
+        return Err(format!(
+            "libvmaf scoring failed: {}",
+            String::from_utf8_lossy(&score.stderr)
+        ));
+    }
+
+    let mean_vmaf = parse_mean_vmaf(&log_path).await;
+
+    let _ = tokio::fs::remove_file(&encoded_path).await;
+    let _ = tokio::fs::remove_file(&log_path).await;
+
+    mean_vmaf
+}
+
+fn path_str(path: &Path) -> Result<&str, String> {
+    path.to_str()
+        .ok_or_else(|| format!("Non-UTF8 probe path: {}", path.display()))
+}
+
+/// Parses the `pooled_metrics.vmaf.mean` value out of a libvmaf JSON log.
+async fn parse_mean_vmaf(log_path: &Path) -> Result<f64, String> {
+    let contents = tokio::fs::read_to_string(log_path)
+        .await
+        .map_err(|e| format!("Failed to read VMAF log: {e}"))?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse VMAF log: {e}"))?;
+
+    json["pooled_metrics"]["vmaf"]["mean"]
+        .as_f64()
+        .ok_or_else(|| "VMAF log missing pooled_metrics.vmaf.mean".to_string())
+}
@@ -1,12 +1,82 @@
 // YouTube video downloader using yt-dlp
+use crate::settings::ToolConfig;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::time::Duration;
 use tauri::Emitter;
 use tokio::process::Command;
 
+/// Default number of attempts before a rate-limited download gives up, used
+/// when a caller doesn't override it.
+const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 5;
+
+/// Base delay (seconds) for the exponential backoff schedule (5s, 15s, 45s, ...)
+const BACKOFF_BASE_SECS: u64 = 5;
+
+/// Multiplier applied to the backoff delay on each successive attempt.
+const BACKOFF_MULTIPLIER: u64 = 3;
+
+/// Upper bound (seconds) for a single backoff sleep
+const BACKOFF_CAP_SECS: u64 = 120;
+
+/// Phrases yt-dlp (or the underlying HTTP client) uses to state an explicit
+/// retry delay, e.g. "retry after 30 seconds" or "try again in 30 seconds".
+const RETRY_AFTER_PHRASES: [&str; 3] = ["retry after", "try again in", "retry in"];
+
+/// Decides whether a failed yt-dlp run looks like a transient rate-limit.
+///
+/// Returns `Some(wait_secs)` when the run should be retried: the inner value is
+/// the exact delay yt-dlp asked us to honour (parsed from an explicit
+/// "retry after"/"try again in" phrase), or `None` when no explicit delay was
+/// given and the caller should fall back to the backoff schedule.
+fn retry_delay_from_stderr(stderr: &str) -> Option<Option<u64>> {
+    let lower = stderr.to_lowercase();
+    let rate_limited = lower.contains("429")
+        || lower.contains("too many request")
+        || lower.contains("technical difficult")
+        || lower.contains("temporarily blocked");
+
+    if !rate_limited {
+        return None;
+    }
+
+    // Only trust a number that follows one of RETRY_AFTER_PHRASES. The
+    // canonical throttle line is "ERROR: ... HTTP Error 429: Too Many
+    // Requests" — its first bare integer is the status code itself, not a
+    // delay, so a naive "first integer on the ERROR line" parse would wait
+    // ~429 seconds on every ordinary rate-limit instead of falling back to
+    // the backoff schedule.
+    let explicit = lower.lines().find_map(|line| {
+        RETRY_AFTER_PHRASES.iter().find_map(|phrase| {
+            let rest = line.split_once(phrase)?.1;
+            rest.split_whitespace()
+                .find_map(|tok| tok.trim_matches(|c: char| !c.is_ascii_digit()).parse::<u64>().ok())
+        })
+    });
+
+    Some(explicit)
+}
+
+/// Sleeps before a retry, emitting a notice so the UI shows why we stalled.
+async fn wait_before_retry(app: &tauri::AppHandle, attempt: u32, max_attempts: u32, explicit: Option<u64>) {
+    let secs = explicit.unwrap_or_else(|| {
+        // Exponential backoff: 5s, 15s, 45s, ... capped.
+        BACKOFF_BASE_SECS
+            .saturating_mul(BACKOFF_MULTIPLIER.saturating_pow(attempt - 1))
+            .min(BACKOFF_CAP_SECS)
+    });
+
+    let _ = app.emit(
+        "export_log",
+        format!("⏳ Rate limited by YouTube. Waiting {secs}s before retry {attempt}/{max_attempts}..."),
+    );
+
+    tokio::time::sleep(Duration::from_secs(secs)).await;
+}
+
 /// Downloads a `YouTube` video using yt-dlp
 /// Returns the path to the downloaded video file
-/// 
+///
 /// Prerequisites: Install yt-dlp
 /// - pip install yt-dlp
 /// - or: sudo apt install yt-dlp
@@ -14,11 +84,14 @@ pub async fn download_youtube_video(
     app: &tauri::AppHandle,
     url: &str,
     output_dir: &str,
+    max_attempts: Option<u32>,
+    tools: &ToolConfig,
 ) -> Result<String, String> {
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_RETRY_ATTEMPTS);
     let _ = app.emit("export_log", format!("Downloading from YouTube: {url}"));
 
     // Check if yt-dlp is installed
-    let check = Command::new("yt-dlp")
+    let check = Command::new(tools.ytdlp())
         .arg("--version")
         .output()
         .await;
@@ -36,48 +109,63 @@ pub async fn download_youtube_video(
 
     let _ = app.emit("export_log", "Starting download...");
 
-    // Run yt-dlp to download the video
-    let child = Command::new("yt-dlp")
-        .args([
-            url,
-            "-f", "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best", // Best MP4 quality
-            "-o", &output_template,
-            "--no-playlist", // Don't download playlists
-            "--progress",    // Show progress
-            "--newline",     // Progress on new lines (easier to parse)
-        ])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start yt-dlp: {e}"))?;
+    for attempt in 1..=max_attempts {
+        // Run yt-dlp to download the video
+        let child = Command::new(tools.ytdlp())
+            .args([
+                url,
+                "-f", "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best", // Best MP4 quality
+                "-o", &output_template,
+                "--no-playlist", // Don't download playlists
+                "--progress",    // Show progress
+                "--newline",     // Progress on new lines (easier to parse)
+            ])
+            .args(&tools.extra_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start yt-dlp: {e}"))?;
 
-    // Wait for process to complete
-    let output = child
-        .wait_with_output()
-        .await
-        .map_err(|e| format!("yt-dlp process error: {e}"))?;
+        // Wait for process to complete
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("yt-dlp process error: {e}"))?;
 
-    // Log stdout
-    if !output.stdout.is_empty() {
-        let stdout_str = String::from_utf8_lossy(&output.stdout);
-        for line in stdout_str.lines() {
+        // Capture stdout and stderr as separate strings
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        // Log stdout
+        for line in stdout.lines() {
             let _ = app.emit("export_log", line);
         }
-    }
 
-    // Log stderr
-    if !output.stderr.is_empty() {
-        let stderr_str = String::from_utf8_lossy(&output.stderr);
-        for line in stderr_str.lines() {
+        // Log stderr
+        for line in stderr.lines() {
             let _ = app.emit("export_log", &format!("yt-dlp: {line}"));
         }
-    }
 
-    if !output.status.success() {
-        let error_msg = if output.stderr.is_empty() {
+        if output.status.success() {
+            break;
+        }
+
+        // Retry only on transient rate-limits; everything else fails fast.
+        if let Some(explicit) = retry_delay_from_stderr(&stderr) {
+            if attempt < max_attempts {
+                wait_before_retry(app, attempt, max_attempts, explicit).await;
+                continue;
+            }
+            return Err(format!(
+                "YouTube download failed after {max_attempts} attempts (rate limited): {}",
+                stderr.trim()
+            ));
+        }
+
+        let error_msg = if stderr.trim().is_empty() {
             "YouTube download failed. Check the URL and try again.".to_string()
         } else {
-            format!("YouTube download failed: {}", String::from_utf8_lossy(&output.stderr))
+            format!("YouTube download failed: {}", stderr.trim())
         };
         return Err(error_msg);
     }
@@ -131,32 +219,269 @@ fn find_latest_video_file(dir: &str) -> Result<PathBuf, String> {
 pub async fn get_video_info(
     app: &tauri::AppHandle,
     url: &str,
+    max_attempts: Option<u32>,
+    tools: &ToolConfig,
 ) -> Result<VideoInfo, String> {
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_RETRY_ATTEMPTS);
     let _ = app.emit("export_log", "Fetching video info...");
 
-    let output = Command::new("yt-dlp")
-        .args([
-            url,
-            "--dump-json",
-            "--no-playlist",
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to get video info: {e}"))?;
+    for attempt in 1..=max_attempts {
+        let output = Command::new(tools.ytdlp())
+            .args([
+                url,
+                "--dump-json",
+                "--no-playlist",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to get video info: {e}"))?
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("Failed to get video info: {e}"))?;
 
-    if !output.status.success() {
-        return Err("Failed to fetch video information".to_string());
+        if output.status.success() {
+            let json_str = String::from_utf8_lossy(&output.stdout);
+            let info: serde_json::Value = serde_json::from_str(&json_str)
+                .map_err(|e| format!("Failed to parse video info: {e}"))?;
+
+            return Ok(parse_video_info(&info));
+        }
+
+        // Retry transient rate-limits, otherwise fail fast with the captured stderr.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Some(explicit) = retry_delay_from_stderr(&stderr) {
+            if attempt < max_attempts {
+                wait_before_retry(app, attempt, max_attempts, explicit).await;
+                continue;
+            }
+            return Err(format!(
+                "Failed to fetch video information after {max_attempts} attempts (rate limited): {}",
+                stderr.trim()
+            ));
+        }
+
+        return Err(if stderr.trim().is_empty() {
+            "Failed to fetch video information".to_string()
+        } else {
+            format!("Failed to fetch video information: {}", stderr.trim())
+        });
     }
 
-    let json_str = String::from_utf8_lossy(&output.stdout);
-    let info: serde_json::Value = serde_json::from_str(&json_str)
-        .map_err(|e| format!("Failed to parse video info: {e}"))?;
+    Err("Failed to fetch video information".to_string())
+}
+
+/// Builds a [`VideoInfo`] from a yt-dlp `--dump-json` document, including the
+/// full format table so the frontend can let the user pick a quality.
+fn parse_video_info(info: &serde_json::Value) -> VideoInfo {
+    let formats = info["formats"]
+        .as_array()
+        .map(|arr| arr.iter().map(parse_format).collect())
+        .unwrap_or_default();
 
-    Ok(VideoInfo {
+    VideoInfo {
         title: info["title"].as_str().unwrap_or("Unknown").to_string(),
         duration: info["duration"].as_f64().unwrap_or(0.0),
         uploader: info["uploader"].as_str().unwrap_or("Unknown").to_string(),
-    })
+        thumbnail: info["thumbnail"].as_str().unwrap_or_default().to_string(),
+        view_count: info["view_count"].as_u64().unwrap_or(0),
+        upload_date: info["upload_date"].as_str().unwrap_or_default().to_string(),
+        formats,
+    }
+}
+
+/// Maps a single entry of yt-dlp's `formats` array into a [`VideoFormat`].
+fn parse_format(fmt: &serde_json::Value) -> VideoFormat {
+    // yt-dlp exposes `resolution` directly on newer versions; fall back to
+    // width x height for older ones.
+    let resolution = fmt["resolution"].as_str().map_or_else(
+        || match (fmt["width"].as_u64(), fmt["height"].as_u64()) {
+            (Some(w), Some(h)) => format!("{w}x{h}"),
+            _ => "audio only".to_string(),
+        },
+        ToString::to_string,
+    );
+
+    VideoFormat {
+        format_id: fmt["format_id"].as_str().unwrap_or_default().to_string(),
+        resolution,
+        fps: fmt["fps"].as_f64(),
+        vcodec: fmt["vcodec"].as_str().unwrap_or("none").to_string(),
+        acodec: fmt["acodec"].as_str().unwrap_or("none").to_string(),
+        // yt-dlp reports `filesize` or the estimated `filesize_approx`.
+        filesize: fmt["filesize"]
+            .as_u64()
+            .or_else(|| fmt["filesize_approx"].as_u64()),
+    }
+}
+
+/// Downloads a `YouTube` video using an explicit yt-dlp `-f` format selector
+/// (e.g. a `format_id` chosen from [`VideoInfo::formats`]).
+pub async fn download_youtube_video_with_format(
+    app: &tauri::AppHandle,
+    url: &str,
+    output_dir: &str,
+    format_id: &str,
+    max_attempts: Option<u32>,
+    tools: &ToolConfig,
+) -> Result<String, String> {
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_RETRY_ATTEMPTS);
+    let _ = app.emit(
+        "export_log",
+        format!("Downloading from YouTube: {url} (format {format_id})"),
+    );
+
+    let check = Command::new(tools.ytdlp()).arg("--version").output().await;
+    if check.is_err() {
+        return Err("yt-dlp not found. Install it with: pip install yt-dlp".to_string());
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {e}"))?;
+
+    let output_template = format!("{output_dir}/%(title)s.%(ext)s");
+
+    let _ = app.emit("export_log", "Starting download...");
+
+    for attempt in 1..=max_attempts {
+        let output = Command::new(tools.ytdlp())
+            .args([
+                url,
+                "-f", format_id,
+                "-o", &output_template,
+                "--no-playlist",
+                "--progress",
+                "--newline",
+            ])
+            .args(&tools.extra_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start yt-dlp: {e}"))?
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("yt-dlp process error: {e}"))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        for line in stdout.lines() {
+            let _ = app.emit("export_log", line);
+        }
+        for line in stderr.lines() {
+            let _ = app.emit("export_log", &format!("yt-dlp: {line}"));
+        }
+
+        if output.status.success() {
+            break;
+        }
+
+        // Retry only on transient rate-limits; everything else fails fast.
+        if let Some(explicit) = retry_delay_from_stderr(&stderr) {
+            if attempt < max_attempts {
+                wait_before_retry(app, attempt, max_attempts, explicit).await;
+                continue;
+            }
+            return Err(format!(
+                "YouTube download failed after {max_attempts} attempts (rate limited): {}",
+                stderr.trim()
+            ));
+        }
+
+        return Err(if stderr.trim().is_empty() {
+            "YouTube download failed. Check the URL and try again.".to_string()
+        } else {
+            format!("YouTube download failed: {}", stderr.trim())
+        });
+    }
+
+    let downloaded_file = find_latest_video_file(output_dir)?;
+    let _ = app.emit(
+        "export_log",
+        format!("✓ Downloaded: {}", downloaded_file.file_name().unwrap().to_string_lossy()),
+    );
+
+    Ok(downloaded_file.to_string_lossy().to_string())
+}
+
+/// Default cap on how many playlist entries are ingested in one run.
+const DEFAULT_PLAYLIST_LIMIT: usize = 50;
+
+/// Downloads a playlist or channel in one shot.
+///
+/// Entries are enumerated first with `--flat-playlist --dump-json` (emitting
+/// the count and titles), then each is downloaded sequentially into
+/// `output_dir` with the per-item rate-limit retry behaviour of
+/// [`download_youtube_video`]. Returns the resolved file paths so the frontend
+/// can queue them through the clip-detection/export flow.
+pub async fn download_youtube_playlist(
+    app: &tauri::AppHandle,
+    url: &str,
+    output_dir: &str,
+    max_count: Option<usize>,
+    tools: &ToolConfig,
+) -> Result<Vec<String>, String> {
+    let limit = max_count.unwrap_or(DEFAULT_PLAYLIST_LIMIT);
+    let _ = app.emit("export_log", format!("Enumerating playlist: {url}"));
+
+    let check = Command::new(tools.ytdlp()).arg("--version").output().await;
+    if check.is_err() {
+        return Err("yt-dlp not found. Install it with: pip install yt-dlp".to_string());
+    }
+
+    // Step 1: flat-enumerate entries (one JSON document per line).
+    let output = Command::new(tools.ytdlp())
+        .args([url, "--flat-playlist", "--dump-json"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start yt-dlp: {e}"))?
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("yt-dlp process error: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to enumerate playlist: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let entries: Vec<(String, String)> = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|entry| {
+            entry["id"]
+                .as_str()
+                .map(|id| (id.to_string(), entry["title"].as_str().unwrap_or("Unknown").to_string()))
+        })
+        .take(limit)
+        .collect();
+
+    let _ = app.emit("export_log", format!("✓ Found {} entries (limit {limit})", entries.len()));
+    for (idx, (_, title)) in entries.iter().enumerate() {
+        let _ = app.emit("export_log", format!("  {}. {title}", idx + 1));
+    }
+
+    // Step 2: download each entry sequentially.
+    let mut downloaded = Vec::new();
+    for (idx, (id, title)) in entries.iter().enumerate() {
+        let _ = app.emit(
+            "export_log",
+            format!("📥 Downloading {}/{}: {title}", idx + 1, entries.len()),
+        );
+        let watch_url = format!("https://www.youtube.com/watch?v={id}");
+        let path = download_youtube_video(app, &watch_url, output_dir, None, tools).await?;
+        downloaded.push(path);
+    }
+
+    let _ = app.emit(
+        "export_log",
+        format!("✅ Playlist download complete: {} files", downloaded.len()),
+    );
+
+    Ok(downloaded)
 }
 
 #[derive(Debug, Clone)]
@@ -164,4 +489,19 @@ pub struct VideoInfo {
     pub title: String,
     pub duration: f64,
     pub uploader: String,
+    pub thumbnail: String,
+    pub view_count: u64,
+    pub upload_date: String,
+    pub formats: Vec<VideoFormat>,
+}
+
+/// A single downloadable format as reported by yt-dlp's `formats` array.
+#[derive(Debug, Clone)]
+pub struct VideoFormat {
+    pub format_id: String,
+    pub resolution: String,
+    pub fps: Option<f64>,
+    pub vcodec: String,
+    pub acodec: String,
+    pub filesize: Option<u64>,
 }